@@ -0,0 +1,494 @@
+//! Run the pallet benchmarks natively against an in-memory state and write the results as
+//! `default_weight.rs`.
+//!
+//! cargo run --release --features runtime-benchmarks --example weights -- \
+//!     --steps 50 --repeat 20 --output src/default_weight.rs
+
+use std::{cell::RefCell, collections::HashMap, fmt::Write as _, fs};
+
+use frame_benchmarking::{Analysis, BenchmarkResults, BenchmarkSelector, Benchmarking, TrackedStorageKey};
+use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
+use frame_system::{EnsureRoot, EnsureSigned};
+use sp_core::{
+	H256,
+	crypto::AccountId32,
+	hashing::twox_128,
+	offchain::storage::OffchainOverlayedChanges,
+	storage::ChildInfo,
+};
+use sp_runtime::{
+	BuildStorage, Perbill,
+	generic::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use sp_state_machine::{
+	Backend, ChildStorageCollection, Ext, InMemoryBackend, OverlayedChanges, StorageCollection,
+	StorageTransactionCache, TrieBackend, UsageInfo,
+};
+
+impl_outer_origin! {
+	pub enum Origin for Runtime {}
+}
+
+mod token {
+	pub use pallet_token::Event;
+}
+
+impl_outer_event! {
+	pub enum Event for Runtime {
+		frame_system<T>,
+		balances<T>,
+		token<T>,
+	}
+}
+
+// A runtime shaped like a production one: 32-byte accounts and 128-bit balances.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Runtime;
+
+type BlockNumber = u32;
+
+parameter_types! {
+	pub const BlockHashCount: BlockNumber = 2400;
+	pub const MaximumBlockWeight: Weight = 2_000_000_000_000;
+	pub const MaximumBlockLength: u32 = 5 * 1024 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl frame_system::Trait for Runtime {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u32;
+	type BlockNumber = BlockNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId32;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header<BlockNumber, BlakeTwo256>;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = balances::AccountData<u128>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u128 = 500;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl balances::Trait for Runtime {
+	type Balance = u128;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Module<Runtime>;
+	type WeightInfo = ();
+	type MaxLocks = MaxLocks;
+}
+
+parameter_types! {
+	pub const AllowMintBurnWhenPaused: bool = false;
+	pub const AllowFrozenReceive: bool = false;
+	pub const TokenDeposit: u128 = 1_000_000;
+	pub const MetadataDepositPerByte: u128 = 10_000;
+	pub const MaxNameLength: u32 = 64;
+	pub const MaxSymbolLength: u32 = 8;
+	pub const MaxIconUriLength: u32 = 256;
+	pub const MaxDescriptionLength: u32 = 1024;
+	pub const MaxWebsiteLength: u32 = 256;
+	pub const MaxBatchSize: u32 = 32;
+	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const MaxProofLength: u32 = 32;
+}
+
+impl pallet_token::Trait for Runtime {
+	type Event = Event;
+	type Currency = balances::Module<Runtime>;
+	type AllowMintBurnWhenPaused = AllowMintBurnWhenPaused;
+	type AllowFrozenReceive = AllowFrozenReceive;
+	type CreateOrigin = EnsureSigned<AccountId32>;
+	type ForceOrigin = EnsureRoot<AccountId32>;
+	type TokenDeposit = TokenDeposit;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type MaxNameLength = MaxNameLength;
+	type MaxSymbolLength = MaxSymbolLength;
+	type MaxIconUriLength = MaxIconUriLength;
+	type MaxDescriptionLength = MaxDescriptionLength;
+	type MaxWebsiteLength = MaxWebsiteLength;
+	type MaxBatchSize = MaxBatchSize;
+	type UnsignedPriority = UnsignedPriority;
+	type MaxProofLength = MaxProofLength;
+	type WeightInfo = ();
+}
+
+type Token = pallet_token::Module<Runtime>;
+
+struct Options {
+	steps: u32,
+	repeat: u32,
+	output: String,
+}
+
+fn options() -> Options {
+	let mut options = Options { steps: 50, repeat: 20, output: "src/default_weight.rs".into() };
+	let mut args = std::env::args().skip(1);
+	while let Some(arg) = args.next() {
+		let value = args.next().unwrap_or_else(|| panic!("missing value for {}", arg));
+		match arg.as_str() {
+			"--steps" => options.steps = value.parse().expect("--steps takes a number"),
+			"--repeat" => options.repeat = value.parse().expect("--repeat takes a number"),
+			"--output" => options.output = value,
+			_ => panic!("unknown argument {}", arg),
+		}
+	}
+	options
+}
+
+// Keys every block touches anyway, as whitelisted by the node's benchmark runtime.
+fn whitelist() -> Vec<TrackedStorageKey> {
+	[("System", "Number"), ("System", "ExecutionPhase"), ("System", "EventCount"), ("System", "Events")]
+		.iter()
+		.chain(&[("Balances", "TotalIssuance")])
+		.map(|(module, item)| [twox_128(module.as_bytes()), twox_128(item.as_bytes())].concat().into())
+		.collect()
+}
+
+type State = InMemoryBackend<BlakeTwo256>;
+
+#[derive(Default, Clone, Copy)]
+struct KeyTracker {
+	has_been_read: bool,
+	has_been_written: bool,
+}
+
+#[derive(Default, Clone, Copy)]
+struct ReadWriteCount {
+	reads: u32,
+	repeat_reads: u32,
+	writes: u32,
+	repeat_writes: u32,
+}
+
+// An in-memory state that can be committed to, wiped back to genesis and counts the first
+// access to each key, like the node's `BenchmarkingState` does on top of its database.
+struct BenchState {
+	genesis: State,
+	state: RefCell<State>,
+	whitelist: RefCell<Vec<TrackedStorageKey>>,
+	keys: RefCell<HashMap<Vec<u8>, KeyTracker>>,
+	count: RefCell<ReadWriteCount>,
+}
+
+impl BenchState {
+	fn new(genesis: sp_core::storage::Storage) -> Self {
+		let genesis = State::from(genesis);
+		BenchState {
+			state: RefCell::new(genesis.clone()),
+			genesis,
+			whitelist: Default::default(),
+			keys: Default::default(),
+			count: Default::default(),
+		}
+	}
+
+	fn reset_keys(&self) {
+		*self.keys.borrow_mut() = self.whitelist.borrow().iter()
+			.map(|k| (k.key.clone(), KeyTracker { has_been_read: k.has_been_read, has_been_written: k.has_been_written }))
+			.collect();
+		*self.count.borrow_mut() = Default::default();
+	}
+
+	// Child tries share the main key space; the pallet does not use any.
+	fn add_read(&self, key: &[u8]) {
+		let mut count = self.count.borrow_mut();
+		let mut keys = self.keys.borrow_mut();
+		let tracker = keys.entry(key.to_vec()).or_default();
+		if tracker.has_been_read {
+			count.repeat_reads += 1;
+		} else {
+			tracker.has_been_read = true;
+			count.reads += 1;
+		}
+	}
+
+	// A written key also counts as read.
+	fn add_write(&self, key: &[u8]) {
+		let mut count = self.count.borrow_mut();
+		let mut keys = self.keys.borrow_mut();
+		let tracker = keys.entry(key.to_vec()).or_default();
+		if tracker.has_been_written {
+			count.repeat_writes += 1;
+		} else {
+			*tracker = KeyTracker { has_been_read: true, has_been_written: true };
+			count.writes += 1;
+		}
+	}
+}
+
+impl std::fmt::Debug for BenchState {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "BenchState")
+	}
+}
+
+impl Backend<BlakeTwo256> for BenchState {
+	type Error = <State as Backend<BlakeTwo256>>::Error;
+	type Transaction = <State as Backend<BlakeTwo256>>::Transaction;
+	type TrieBackendStorage = <State as Backend<BlakeTwo256>>::TrieBackendStorage;
+
+	fn storage(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		self.add_read(key);
+		self.state.borrow().storage(key)
+	}
+
+	fn storage_hash(&self, key: &[u8]) -> Result<Option<H256>, Self::Error> {
+		self.add_read(key);
+		self.state.borrow().storage_hash(key)
+	}
+
+	fn child_storage(&self, child_info: &ChildInfo, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		self.add_read(key);
+		self.state.borrow().child_storage(child_info, key)
+	}
+
+	fn exists_storage(&self, key: &[u8]) -> Result<bool, Self::Error> {
+		self.add_read(key);
+		self.state.borrow().exists_storage(key)
+	}
+
+	fn exists_child_storage(&self, child_info: &ChildInfo, key: &[u8]) -> Result<bool, Self::Error> {
+		self.add_read(key);
+		self.state.borrow().exists_child_storage(child_info, key)
+	}
+
+	fn next_storage_key(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		self.add_read(key);
+		self.state.borrow().next_storage_key(key)
+	}
+
+	fn next_child_storage_key(&self, child_info: &ChildInfo, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		self.add_read(key);
+		self.state.borrow().next_child_storage_key(child_info, key)
+	}
+
+	fn for_keys_with_prefix<F: FnMut(&[u8])>(&self, prefix: &[u8], f: F) {
+		self.state.borrow().for_keys_with_prefix(prefix, f)
+	}
+
+	fn for_key_values_with_prefix<F: FnMut(&[u8], &[u8])>(&self, prefix: &[u8], f: F) {
+		self.state.borrow().for_key_values_with_prefix(prefix, f)
+	}
+
+	fn for_keys_in_child_storage<F: FnMut(&[u8])>(&self, child_info: &ChildInfo, f: F) {
+		self.state.borrow().for_keys_in_child_storage(child_info, f)
+	}
+
+	fn for_child_keys_with_prefix<F: FnMut(&[u8])>(&self, child_info: &ChildInfo, prefix: &[u8], f: F) {
+		self.state.borrow().for_child_keys_with_prefix(child_info, prefix, f)
+	}
+
+	fn storage_root<'a>(
+		&self,
+		delta: impl Iterator<Item = (&'a [u8], Option<&'a [u8]>)>,
+	) -> (H256, Self::Transaction) {
+		self.state.borrow().storage_root(delta)
+	}
+
+	fn child_storage_root<'a>(
+		&self,
+		child_info: &ChildInfo,
+		delta: impl Iterator<Item = (&'a [u8], Option<&'a [u8]>)>,
+	) -> (H256, bool, Self::Transaction) {
+		self.state.borrow().child_storage_root(child_info, delta)
+	}
+
+	fn pairs(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
+		self.state.borrow().pairs()
+	}
+
+	fn keys(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
+		self.state.borrow().keys(prefix)
+	}
+
+	fn child_keys(&self, child_info: &ChildInfo, prefix: &[u8]) -> Vec<Vec<u8>> {
+		self.state.borrow().child_keys(child_info, prefix)
+	}
+
+	fn as_trie_backend(&mut self) -> Option<&TrieBackend<Self::TrieBackendStorage, BlakeTwo256>> {
+		None
+	}
+
+	fn register_overlay_stats(&mut self, _: &sp_state_machine::StateMachineStats) {}
+
+	fn usage_info(&self) -> UsageInfo {
+		UsageInfo::empty()
+	}
+
+	fn commit(
+		&self,
+		storage_root: H256,
+		transaction: Self::Transaction,
+		main_storage_changes: StorageCollection,
+		child_storage_changes: ChildStorageCollection,
+	) -> Result<(), Self::Error> {
+		let state = self.state.borrow().update_backend(storage_root, transaction);
+		*self.state.borrow_mut() = state;
+		main_storage_changes.iter()
+			.chain(child_storage_changes.iter().flat_map(|(_, changes)| changes))
+			.for_each(|(key, _)| self.add_write(key));
+		Ok(())
+	}
+
+	fn wipe(&self) -> Result<(), Self::Error> {
+		*self.state.borrow_mut() = self.genesis.clone();
+		self.reset_keys();
+		Ok(())
+	}
+
+	fn read_write_count(&self) -> (u32, u32, u32, u32) {
+		let count = *self.count.borrow();
+		(count.reads, count.repeat_reads, count.writes, count.repeat_writes)
+	}
+
+	fn reset_read_write_count(&self) {
+		self.reset_keys()
+	}
+
+	fn get_whitelist(&self) -> Vec<TrackedStorageKey> {
+		self.whitelist.borrow().clone()
+	}
+
+	fn set_whitelist(&self, new: Vec<TrackedStorageKey>) {
+		*self.whitelist.borrow_mut() = new;
+	}
+}
+
+fn run(state: &BenchState, name: &[u8], options: &Options) -> Vec<BenchmarkResults> {
+	let mut overlay = OverlayedChanges::default();
+	let mut offchain_overlay = OffchainOverlayedChanges::disabled();
+	let mut cache = StorageTransactionCache::default();
+	overlay.enter_runtime().expect("a fresh overlay is outside the runtime");
+	let mut ext = Ext::<BlakeTwo256, BlockNumber, _>::new(
+		&mut overlay,
+		&mut offchain_overlay,
+		&mut cache,
+		state,
+		None,
+		None,
+	);
+	sp_externalities::set_and_run_with_externalities(&mut ext, || {
+		Token::run_benchmark(name, &[], &[], &[options.steps], options.repeat, &whitelist(), false)
+	})
+	.unwrap_or_else(|e| panic!("benchmark {} failed: {}", String::from_utf8_lossy(name), e))
+}
+
+// Group the digits of `n` in threes, as the benchmark CLI does.
+fn underscore(n: u128) -> String {
+	let digits = n.to_string();
+	let mut out = String::new();
+	for (i, c) in digits.chars().enumerate() {
+		if i > 0 && (digits.len() - i).is_multiple_of(3) {
+			out.push('_');
+		}
+		out.push(c);
+	}
+	out
+}
+
+fn write_weight(out: &mut String, name: &str, results: &[BenchmarkResults]) {
+	let results = results.to_vec();
+	let analyse = |selector| Analysis::min_squares_iqr(&results, selector).expect("benchmark results fit a model");
+	let time = analyse(BenchmarkSelector::ExtrinsicTime);
+	let reads = analyse(BenchmarkSelector::Reads);
+	let writes = analyse(BenchmarkSelector::Writes);
+	let slopes = |analysis: &Analysis| {
+		analysis.names.iter().cloned().zip(analysis.slopes.iter().cloned()).filter(|(_, s)| *s != 0).collect::<Vec<_>>()
+	};
+
+	let used = |component: &String| {
+		[&time, &reads, &writes].iter().any(|a| slopes(a).iter().any(|(n, _)| n == component))
+	};
+	let params = time.names.iter()
+		.map(|c| if used(c) { format!("{}: u32", c) } else { format!("_{}: u32", c) })
+		.collect::<Vec<_>>()
+		.join(", ");
+
+	writeln!(out, "\tfn {}({}) -> Weight {{", name, params).unwrap();
+	writeln!(out, "\t\t({} as Weight)", underscore(time.base * 1000)).unwrap();
+	for (c, slope) in slopes(&time) {
+		writeln!(out, "\t\t\t.saturating_add(({} as Weight).saturating_mul({} as Weight))", underscore(slope * 1000), c).unwrap();
+	}
+	for (kind, analysis) in [("reads", &reads), ("writes", &writes)].iter() {
+		if analysis.base != 0 {
+			writeln!(out, "\t\t\t.saturating_add(DbWeight::get().{}({} as Weight))", kind, analysis.base).unwrap();
+		}
+		for (c, slope) in slopes(analysis) {
+			writeln!(
+				out,
+				"\t\t\t.saturating_add(DbWeight::get().{}(({} as Weight).saturating_mul({} as Weight)))",
+				kind, slope, c,
+			).unwrap();
+		}
+	}
+	writeln!(out, "\t}}").unwrap();
+}
+
+fn main() {
+	let options = options();
+	let genesis = GenesisConfig::default().build_storage().expect("genesis builds");
+	let state = BenchState::new(genesis);
+
+	let mut out = String::new();
+	writeln!(out, "//! Weights for the Token Pallet").unwrap();
+	writeln!(out, "//! THIS FILE WAS GENERATED BY `examples/weights.rs`.").unwrap();
+	writeln!(
+		out,
+		"//! DATE: {}, STEPS: [{}], REPEAT: {}, EXECUTION: Native, DB: in-memory",
+		chrono::Utc::now().format("%Y-%m-%d"),
+		options.steps,
+		options.repeat,
+	).unwrap();
+	writeln!(out).unwrap();
+	writeln!(out, "// Executed Command:").unwrap();
+	writeln!(out, "// cargo run --release --features runtime-benchmarks --example weights --").unwrap();
+	writeln!(out, "// --steps {} --repeat {} --output {}", options.steps, options.repeat, options.output).unwrap();
+	writeln!(out).unwrap();
+	writeln!(out, "use frame_support::weights::{{Weight, constants::RocksDbWeight as DbWeight}};").unwrap();
+	writeln!(out).unwrap();
+	writeln!(out, "impl crate::WeightInfo for () {{").unwrap();
+	for name in Token::benchmarks(false) {
+		let label = String::from_utf8_lossy(name);
+		eprintln!("benchmarking {}", label);
+		let results = run(&state, name, &options);
+		write_weight(&mut out, &label, &results);
+	}
+	writeln!(out, "}}").unwrap();
+
+	fs::write(&options.output, out).expect("weights file is writable");
+}
+
+// `GenesisConfig` for the runtime above.
+#[derive(Default)]
+struct GenesisConfig {
+	system: frame_system::GenesisConfig,
+	token: pallet_token::GenesisConfig<Runtime>,
+}
+
+impl BuildStorage for GenesisConfig {
+	fn assimilate_storage(&self, storage: &mut sp_core::storage::Storage) -> Result<(), String> {
+		self.system.assimilate_storage::<Runtime>(storage)?;
+		self.token.assimilate_storage(storage)
+	}
+}
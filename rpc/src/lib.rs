@@ -0,0 +1,150 @@
+//! RPC interface for the token pallet.
+//!
+//! Exposes the `TokenApi` runtime API as `token_*` JSON-RPC methods. Every method takes an
+//! optional block hash and falls back to the best block. Balances are returned as
+//! `NumberOrHex`, so that JavaScript clients can read amounts above 2^53 exactly.
+
+use std::sync::Arc;
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use parity_scale_codec::Codec;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::U256;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_token_runtime_api::TokenApi as TokenRuntimeApi;
+use pallet_token_runtime_api::{TokenIndex, TokenInfo};
+
+#[rpc]
+pub trait TokenApi<BlockHash, AccountId, Balance, BlockNumber> {
+	/// The balance of `who` in `token`.
+	#[rpc(name = "token_balanceOf")]
+	fn balance_of(&self, token: TokenIndex, who: AccountId, at: Option<BlockHash>) -> Result<NumberOrHex>;
+
+	/// The total issuance of `token`.
+	#[rpc(name = "token_totalSupply")]
+	fn total_supply(&self, token: TokenIndex, at: Option<BlockHash>) -> Result<NumberOrHex>;
+
+	/// The amount `spender` may still transfer out of `owner`'s balance of `token`.
+	#[rpc(name = "token_allowance")]
+	fn allowance(
+		&self,
+		token: TokenIndex,
+		owner: AccountId,
+		spender: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<NumberOrHex>;
+
+	/// Metadata of `token`, or `null` if it does not exist.
+	#[rpc(name = "token_tokenInfo")]
+	fn token_info(&self, token: TokenIndex, at: Option<BlockHash>) -> Result<Option<TokenInfo<Balance, BlockNumber>>>;
+
+	/// Every token held by `who`, with the balance of each.
+	#[rpc(name = "token_tokensOf")]
+	fn tokens_of(&self, who: AccountId, at: Option<BlockHash>) -> Result<Vec<(TokenIndex, NumberOrHex)>>;
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query token state.".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+/// The largest integer a JavaScript number holds exactly.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// A balance as a plain number when JavaScript can hold it exactly, and as hex otherwise.
+fn number_or_hex(balance: impl Into<U256>) -> NumberOrHex {
+	let balance = balance.into();
+	if balance <= U256::from(MAX_SAFE_INTEGER) {
+		NumberOrHex::Number(balance.low_u64())
+	} else {
+		NumberOrHex::Hex(balance)
+	}
+}
+
+/// Implements the `TokenApi` RPC trait on top of a client exposing `TokenRuntimeApi`.
+pub struct Token<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Token<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Token { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> Token<C, Block>
+where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+	}
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+	TokenApi<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber> for Token<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TokenRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	AccountId: Codec,
+	Balance: Codec + Serialize + DeserializeOwned + Into<U256>,
+	BlockNumber: Codec + Serialize + DeserializeOwned,
+{
+	fn balance_of(&self, token: TokenIndex, who: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<NumberOrHex> {
+		self.client.runtime_api()
+			.balance_of(&self.block_id(at), token, who)
+			.map(number_or_hex)
+			.map_err(runtime_error)
+	}
+
+	fn total_supply(&self, token: TokenIndex, at: Option<<Block as BlockT>::Hash>) -> Result<NumberOrHex> {
+		self.client.runtime_api()
+			.total_supply(&self.block_id(at), token)
+			.map(number_or_hex)
+			.map_err(runtime_error)
+	}
+
+	fn allowance(
+		&self,
+		token: TokenIndex,
+		owner: AccountId,
+		spender: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<NumberOrHex> {
+		self.client.runtime_api()
+			.allowance(&self.block_id(at), token, owner, spender)
+			.map(number_or_hex)
+			.map_err(runtime_error)
+	}
+
+	fn token_info(
+		&self,
+		token: TokenIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<TokenInfo<Balance, BlockNumber>>> {
+		self.client.runtime_api()
+			.token_info(&self.block_id(at), token)
+			.map_err(runtime_error)
+	}
+
+	fn tokens_of(&self, who: AccountId, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(TokenIndex, NumberOrHex)>> {
+		self.client.runtime_api()
+			.tokens_of(&self.block_id(at), who)
+			.map(|tokens| tokens.into_iter().map(|(token, balance)| (token, number_or_hex(balance))).collect())
+			.map_err(runtime_error)
+	}
+}
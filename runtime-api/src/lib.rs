@@ -0,0 +1,26 @@
+//! Runtime API definition for the token pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The call wrappers generated by `decl_runtime_apis!` take many arguments.
+#![allow(clippy::too_many_arguments)]
+
+use parity_scale_codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_token::{TokenIndex, TokenInfo};
+
+sp_api::decl_runtime_apis! {
+	/// Read-only queries over token balances, allowances and metadata.
+	pub trait TokenApi<AccountId: Codec, Balance: Codec, BlockNumber: Codec> {
+		/// The balance of `who` in `token`.
+		fn balance_of(token: TokenIndex, who: AccountId) -> Balance;
+		/// The total issuance of `token`.
+		fn total_supply(token: TokenIndex) -> Balance;
+		/// The amount `spender` may still transfer out of `owner`'s balance of `token`.
+		fn allowance(token: TokenIndex, owner: AccountId, spender: AccountId) -> Balance;
+		/// Metadata of `token`, if it exists.
+		fn token_info(token: TokenIndex) -> Option<TokenInfo<Balance, BlockNumber>>;
+		/// Every token held by `who`, with the balance of each.
+		fn tokens_of(who: AccountId) -> Vec<(TokenIndex, Balance)>;
+	}
+}
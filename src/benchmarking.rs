@@ -0,0 +1,524 @@
+//! Token pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+// The test functions generated by `benchmarks!` repeat the `Trait` bound in a where clause.
+#![allow(clippy::multiple_bound_locations)]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use frame_support::traits::UnfilteredDispatchable;
+use sp_runtime::traits::Bounded;
+
+use crate::Module as Token;
+
+const SEED: u32 = 0;
+
+// A valid symbol unique to `i`: "T" followed by the digits of `i`.
+fn numbered_symbol(i: u32) -> Vec<u8> {
+	let mut digits = Vec::new();
+	let mut rest = i;
+	loop {
+		digits.push(b'0' + (rest % 10) as u8);
+		rest /= 10;
+		if rest == 0 {
+			break;
+		}
+	}
+	digits.push(b'T');
+	digits.reverse();
+	digits
+}
+
+// Resolve an origin accepted by `CreateOrigin` and give its account enough funds for the deposit.
+fn funded_creator<T: Trait>() -> (T::Origin, T::AccountId) {
+	let origin = T::CreateOrigin::successful_origin();
+	let creator = T::CreateOrigin::ensure_origin(origin.clone())
+		.map_err(|_| "CreateOrigin rejected its own successful origin")
+		.unwrap();
+	T::Currency::make_free_balance_be(&creator, BalanceOf::<T>::max_value());
+	(origin, creator)
+}
+
+// Create token 0 owned by `owner`, holding the whole initial supply. The token is sufficient,
+// so the benchmark accounts can receive it without a native balance.
+fn create_token<T: Trait>(owner: &T::AccountId, depositor: Option<T::AccountId>) -> TokenIndex {
+	let token = Token::<T>::create_(
+		owner.clone(),
+		b"Benchmark Token".to_vec(),
+		b"BENCH".to_vec(),
+		12,
+		1_000_000u32.into(),
+		None,
+		Zero::zero(),
+		depositor,
+	).unwrap();
+	<IsSufficient>::insert(token, true);
+	token
+}
+
+benchmarks! {
+	_ { }
+
+	create {
+		let n in 1 .. T::MaxNameLength::get();
+		let s in 1 .. T::MaxSymbolLength::get();
+		let (origin, creator) = funded_creator::<T>();
+		let name = vec![b'N'; n as usize];
+		let symbol = vec![b'S'; s as usize];
+		let call = Call::<T>::create(creator.clone(), name, symbol, 12, 1_000_000u32.into(), None, Zero::zero());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Token::<T>::owner(0), Some(creator));
+	}
+
+	force_create {
+		let n in 1 .. T::MaxNameLength::get();
+		let s in 1 .. T::MaxSymbolLength::get();
+		let origin = T::ForceOrigin::successful_origin();
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let name = vec![b'N'; n as usize];
+		let symbol = vec![b'S'; s as usize];
+		let call = Call::<T>::force_create(owner.clone(), name, symbol, 12, 1_000_000u32.into(), None, Zero::zero());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_eq!(Token::<T>::owner(0), Some(owner));
+	}
+
+	transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+	}: _(RawOrigin::Signed(caller), token, recipient.clone(), 100u32.into())
+	verify {
+		assert_eq!(Token::<T>::balance(token, &recipient), 100u32.into());
+	}
+
+	transfer_keep_alive {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+	}: _(RawOrigin::Signed(caller), token, recipient.clone(), 100u32.into())
+	verify {
+		assert_eq!(Token::<T>::balance(token, &recipient), 100u32.into());
+	}
+
+	transfer_batch {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let transfers = (0 .. n)
+			.map(|i| (account("recipient", i, SEED), 100u32.into()))
+			.collect::<Vec<(T::AccountId, BalanceOf<T>)>>();
+	}: _(RawOrigin::Signed(caller), token, transfers)
+	verify {
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		assert_eq!(Token::<T>::balance(token, &recipient), 100u32.into());
+	}
+
+	transfer_multi {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		// A distinct token for every leg, each with a new recipient.
+		let transfers = (0 .. n)
+			.map(|i| {
+				let token = Token::<T>::create_(
+					caller.clone(),
+					b"Benchmark Token".to_vec(),
+					numbered_symbol(i),
+					12,
+					1_000_000u32.into(),
+					None,
+					Zero::zero(),
+					None,
+				).unwrap();
+				<IsSufficient>::insert(token, true);
+				(token, account("recipient", i, SEED), 100u32.into())
+			})
+			.collect::<Vec<(TokenIndex, T::AccountId, BalanceOf<T>)>>();
+	}: _(RawOrigin::Signed(caller), transfers)
+	verify {
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		assert_eq!(Token::<T>::balance(0, &recipient), 100u32.into());
+	}
+
+	transfer_from {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let token = create_token::<T>(&owner, None);
+		let spender: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		Token::<T>::approve_(token, owner.clone(), spender.clone(), 1_000u32.into())?;
+	}: _(RawOrigin::Signed(spender.clone()), token, owner.clone(), recipient.clone(), 100u32.into())
+	verify {
+		assert_eq!(Token::<T>::balance(token, &recipient), 100u32.into());
+		assert_eq!(Token::<T>::approval(token, (&owner, &spender)), 900u32.into());
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let spender: T::AccountId = account("spender", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), token, spender.clone(), 100u32.into())
+	verify {
+		assert_eq!(Token::<T>::approval(token, (&caller, &spender)), 100u32.into());
+	}
+
+	increase_allowance {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let spender: T::AccountId = account("spender", 0, SEED);
+		Token::<T>::approve_(token, caller.clone(), spender.clone(), 100u32.into())?;
+	}: _(RawOrigin::Signed(caller.clone()), token, spender.clone(), 100u32.into())
+	verify {
+		assert_eq!(Token::<T>::approval(token, (&caller, &spender)), 200u32.into());
+	}
+
+	decrease_allowance {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let spender: T::AccountId = account("spender", 0, SEED);
+		Token::<T>::approve_(token, caller.clone(), spender.clone(), 100u32.into())?;
+	}: _(RawOrigin::Signed(caller.clone()), token, spender.clone(), 40u32.into())
+	verify {
+		assert_eq!(Token::<T>::approval(token, (&caller, &spender)), 60u32.into());
+	}
+
+	pause {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+	}: _(RawOrigin::Signed(caller), token, true)
+	verify {
+		assert!(Token::<T>::paused(token));
+	}
+
+	unpause {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		Token::<T>::set_paused(token, true)?;
+	}: _(RawOrigin::Signed(caller), token)
+	verify {
+		assert!(!Token::<T>::paused(token));
+	}
+
+	mint {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+	}: _(RawOrigin::Signed(caller), token, beneficiary.clone(), 1_000u32.into())
+	verify {
+		assert_eq!(Token::<T>::balance(token, &beneficiary), 1_000u32.into());
+	}
+
+	burn {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+	}: _(RawOrigin::Signed(caller.clone()), token, 1_000u32.into())
+	verify {
+		assert_eq!(Token::<T>::supply(token), 999_000u32.into());
+	}
+
+	burn_from {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let who: T::AccountId = account("who", 0, SEED);
+		Token::<T>::transfer_(token, caller.clone(), who.clone(), 1_000u32.into(), false)?;
+	}: _(RawOrigin::Signed(caller), token, who.clone(), 1_000u32.into())
+	verify {
+		assert_eq!(Token::<T>::balance(token, &who), 0u32.into());
+	}
+
+	burn_own {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let token = create_token::<T>(&owner, None);
+		let caller: T::AccountId = whitelisted_caller();
+		Token::<T>::transfer_(token, owner, caller.clone(), 1_000u32.into(), false)?;
+	}: _(RawOrigin::Signed(caller.clone()), token, 1_000u32.into())
+	verify {
+		assert_eq!(Token::<T>::balance(token, &caller), 0u32.into());
+	}
+
+	burn_with_allowance {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let token = create_token::<T>(&owner, None);
+		let spender: T::AccountId = whitelisted_caller();
+		Token::<T>::approve_(token, owner.clone(), spender.clone(), 1_000u32.into())?;
+	}: _(RawOrigin::Signed(spender.clone()), token, owner.clone(), 100u32.into())
+	verify {
+		assert_eq!(Token::<T>::supply(token), 999_900u32.into());
+		assert_eq!(Token::<T>::approval(token, (&owner, &spender)), 900u32.into());
+	}
+
+	transfer_ownership {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let new_owner: T::AccountId = account("new_owner", 0, SEED);
+	}: _(RawOrigin::Signed(caller), token, new_owner.clone())
+	verify {
+		assert_eq!(Token::<T>::pending_owner(token), Some(new_owner));
+	}
+
+	accept_ownership {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let token = create_token::<T>(&owner, None);
+		let caller: T::AccountId = whitelisted_caller();
+		<PendingOwner<T>>::insert(token, &caller);
+	}: _(RawOrigin::Signed(caller.clone()), token)
+	verify {
+		assert_eq!(Token::<T>::owner(token), Some(caller));
+	}
+
+	renounce_ownership {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+	}: _(RawOrigin::Signed(caller), token)
+	verify {
+		assert_eq!(Token::<T>::owner(token), None);
+	}
+
+	set_metadata {
+		let b in 0 .. T::MaxDescriptionLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let token = create_token::<T>(&caller, Some(caller.clone()));
+		let description = vec![b'D'; b as usize];
+	}: _(RawOrigin::Signed(caller), token, None, description.clone(), Vec::new())
+	verify {
+		assert_eq!(Token::<T>::tokens(token).unwrap().description, description);
+	}
+
+	reserve_symbol {
+		let s in 1 .. T::MaxSymbolLength::get();
+		let origin = T::ForceOrigin::successful_origin();
+		let symbol = vec![b'S'; s as usize];
+		let call = Call::<T>::reserve_symbol(symbol.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Token::<T>::symbol_reserved(symbol));
+	}
+
+	release_symbol {
+		let s in 1 .. T::MaxSymbolLength::get();
+		let origin = T::ForceOrigin::successful_origin();
+		let symbol = vec![b'S'; s as usize];
+		<ReservedSymbols>::insert(&symbol, true);
+		let call = Call::<T>::release_symbol(symbol.clone());
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(!Token::<T>::symbol_reserved(symbol));
+	}
+
+	set_sufficient {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let token = create_token::<T>(&owner, None);
+		let origin = T::ForceOrigin::successful_origin();
+		let call = Call::<T>::set_sufficient(token, true);
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert!(Token::<T>::is_sufficient(token));
+	}
+
+	start_destroy {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+	}: _(RawOrigin::Signed(caller), token)
+	verify {
+		assert!(Token::<T>::destroying(token));
+	}
+
+	destroy_accounts {
+		let n in 1 .. 1_000;
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let token = create_token::<T>(&owner, None);
+		for i in 1 .. n {
+			let holder: T::AccountId = account("holder", i, SEED);
+			Token::<T>::transfer_(token, owner.clone(), holder, 1u32.into(), false)?;
+		}
+		<Destroying>::insert(token, true);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), token, n)
+	verify {
+		assert_eq!(Token::<T>::supply(token), 0u32.into());
+	}
+
+	destroy_approvals {
+		let n in 1 .. 1_000;
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let token = create_token::<T>(&owner, None);
+		for i in 0 .. n {
+			let spender: T::AccountId = account("spender", i, SEED);
+			Token::<T>::approve_(token, owner.clone(), spender, 1u32.into())?;
+		}
+		<Destroying>::insert(token, true);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), token, n)
+	verify {
+		assert!(<Approval<T>>::iter_prefix(token).next().is_none());
+	}
+
+	destroy_frozen {
+		let n in 1 .. 1_000;
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let token = create_token::<T>(&owner, None);
+		for i in 0 .. n {
+			let who: T::AccountId = account("frozen", i, SEED);
+			<Frozen<T>>::insert(token, who, true);
+		}
+		<Destroying>::insert(token, true);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), token, n)
+	verify {
+		assert!(<Frozen<T>>::iter_prefix(token).next().is_none());
+	}
+
+	finish_destroy {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let token = create_token::<T>(&caller, Some(caller.clone()));
+		Token::<T>::set_balance(token, &caller, Zero::zero());
+		<Destroying>::insert(token, true);
+	}: _(RawOrigin::Signed(caller), token)
+	verify {
+		assert_eq!(Token::<T>::tokens(token), None);
+	}
+
+	freeze {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let who: T::AccountId = account("who", 0, SEED);
+	}: _(RawOrigin::Signed(caller), token, who.clone())
+	verify {
+		assert!(Token::<T>::frozen(token, &who));
+	}
+
+	thaw {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let who: T::AccountId = account("who", 0, SEED);
+		<Frozen<T>>::insert(token, &who, true);
+	}: _(RawOrigin::Signed(caller), token, who.clone())
+	verify {
+		assert!(!Token::<T>::frozen(token, &who));
+	}
+
+	set_team {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		let issuer: T::AccountId = account("issuer", 0, SEED);
+		let admin: T::AccountId = account("admin", 0, SEED);
+		let freezer: T::AccountId = account("freezer", 0, SEED);
+		let pauser: T::AccountId = account("pauser", 0, SEED);
+	}: _(RawOrigin::Signed(caller), token, issuer.clone(), admin, freezer, pauser)
+	verify {
+		assert_eq!(Token::<T>::team(token).unwrap().issuer, issuer);
+	}
+
+	set_max_supply {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+	}: _(RawOrigin::Signed(caller), token, 2_000_000u32.into())
+	verify {
+		assert_eq!(Token::<T>::tokens(token).unwrap().max_supply, Some(2_000_000u32.into()));
+	}
+
+	create_airdrop {
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+	}: _(RawOrigin::Signed(caller), token, T::Hash::default(), 1_000u32.into(), 100u32.into())
+	verify {
+		assert_eq!(Token::<T>::airdrops(0).unwrap().remaining, 1_000u32.into());
+	}
+
+	claim {
+		let p in 0 .. T::MaxProofLength::get();
+		let issuer: T::AccountId = account("issuer", 0, SEED);
+		let token = create_token::<T>(&issuer, None);
+		let who: T::AccountId = account("who", 0, SEED);
+		let amount: BalanceOf<T> = 100u32.into();
+		let proof = (0 .. p).map(|i| T::Hashing::hash_of(&i)).collect::<Vec<_>>();
+		let leaf = T::Hashing::hash_of(&(&who, amount));
+		<Airdrops<T>>::insert(0, AirdropInfo {
+			token,
+			issuer,
+			merkle_root: Token::<T>::merkle_root(leaf, &proof),
+			remaining: amount,
+			expiry: 100u32.into(),
+			claims: 0,
+		});
+	}: _(RawOrigin::None, 0, who.clone(), amount, proof)
+	verify {
+		assert_eq!(Token::<T>::balance(token, &who), amount);
+	}
+
+	reclaim {
+		let c in 0 .. 1_000;
+		let caller: T::AccountId = whitelisted_caller();
+		let token = create_token::<T>(&caller, None);
+		for i in 0 .. c {
+			let claimant: T::AccountId = account("claimant", i, SEED);
+			<Claimed<T>>::insert(0, claimant, true);
+		}
+		<Airdrops<T>>::insert(0, AirdropInfo {
+			token,
+			issuer: caller.clone(),
+			merkle_root: T::Hash::default(),
+			remaining: 1_000u32.into(),
+			expiry: 0u32.into(),
+			claims: c,
+		});
+		frame_system::Module::<T>::set_block_number(1u32.into());
+	}: _(RawOrigin::Signed(caller), 0, c)
+	verify {
+		assert_eq!(Token::<T>::airdrops(0), None);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create::<Test>());
+			assert_ok!(test_benchmark_force_create::<Test>());
+			assert_ok!(test_benchmark_transfer::<Test>());
+			assert_ok!(test_benchmark_transfer_keep_alive::<Test>());
+			assert_ok!(test_benchmark_transfer_batch::<Test>());
+			assert_ok!(test_benchmark_transfer_multi::<Test>());
+			assert_ok!(test_benchmark_transfer_from::<Test>());
+			assert_ok!(test_benchmark_approve::<Test>());
+			assert_ok!(test_benchmark_increase_allowance::<Test>());
+			assert_ok!(test_benchmark_decrease_allowance::<Test>());
+			assert_ok!(test_benchmark_pause::<Test>());
+			assert_ok!(test_benchmark_unpause::<Test>());
+			assert_ok!(test_benchmark_mint::<Test>());
+			assert_ok!(test_benchmark_burn::<Test>());
+			assert_ok!(test_benchmark_burn_from::<Test>());
+			assert_ok!(test_benchmark_burn_own::<Test>());
+			assert_ok!(test_benchmark_burn_with_allowance::<Test>());
+			assert_ok!(test_benchmark_transfer_ownership::<Test>());
+			assert_ok!(test_benchmark_accept_ownership::<Test>());
+			assert_ok!(test_benchmark_renounce_ownership::<Test>());
+			assert_ok!(test_benchmark_set_metadata::<Test>());
+			assert_ok!(test_benchmark_reserve_symbol::<Test>());
+			assert_ok!(test_benchmark_release_symbol::<Test>());
+			assert_ok!(test_benchmark_set_sufficient::<Test>());
+			assert_ok!(test_benchmark_start_destroy::<Test>());
+			assert_ok!(test_benchmark_destroy_accounts::<Test>());
+			assert_ok!(test_benchmark_destroy_approvals::<Test>());
+			assert_ok!(test_benchmark_destroy_frozen::<Test>());
+			assert_ok!(test_benchmark_finish_destroy::<Test>());
+			assert_ok!(test_benchmark_freeze::<Test>());
+			assert_ok!(test_benchmark_thaw::<Test>());
+			assert_ok!(test_benchmark_set_team::<Test>());
+			assert_ok!(test_benchmark_set_max_supply::<Test>());
+			assert_ok!(test_benchmark_create_airdrop::<Test>());
+			assert_ok!(test_benchmark_claim::<Test>());
+			assert_ok!(test_benchmark_reclaim::<Test>());
+		});
+	}
+}
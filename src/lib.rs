@@ -1,37 +1,234 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+// `decl_module!` needs a deeper recursion limit for the number of calls in this pallet.
+#![recursion_limit = "256"]
+#![allow(clippy::unused_unit)]
+// `create` and its helper take every token parameter individually.
+#![allow(clippy::too_many_arguments)]
 
 use frame_support::{
-	decl_error, decl_event, decl_module, decl_storage, ensure, dispatch::DispatchResult,
+	decl_error, decl_event, decl_module, decl_storage, ensure, transactional,
+	dispatch::{DispatchError, DispatchResult},
+	storage::{IterableStorageDoubleMap, IterableStorageMap},
+	weights::Weight,
 	traits::{
 		Currency, 
+		EnsureOrigin,
+		Get,
 		ReservableCurrency, 
+		StoredMap,
 	},
 };
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_none, ensure_signed};
 use parity_scale_codec::{Decode, Encode};
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{CheckedAdd, CheckedSub, Hash, SaturatedConversion, Saturating, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
+	},
+};
 use sp_std::prelude::*;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+mod default_weight;
+mod migrations;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
 
 #[cfg(test)]
 mod tests;
 
+pub trait WeightInfo {
+	fn create(n: u32, s: u32) -> Weight;
+	fn force_create(n: u32, s: u32) -> Weight;
+	fn transfer() -> Weight;
+	fn transfer_keep_alive() -> Weight;
+	fn transfer_batch(n: u32) -> Weight;
+	fn transfer_multi(n: u32) -> Weight;
+	fn transfer_from() -> Weight;
+	fn approve() -> Weight;
+	fn increase_allowance() -> Weight;
+	fn decrease_allowance() -> Weight;
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+	fn mint() -> Weight;
+	fn burn() -> Weight;
+	fn burn_from() -> Weight;
+	fn burn_own() -> Weight;
+	fn burn_with_allowance() -> Weight;
+	fn transfer_ownership() -> Weight;
+	fn accept_ownership() -> Weight;
+	fn renounce_ownership() -> Weight;
+	fn set_metadata(b: u32) -> Weight;
+	fn reserve_symbol(s: u32) -> Weight;
+	fn release_symbol(s: u32) -> Weight;
+	fn set_sufficient() -> Weight;
+	fn create_airdrop() -> Weight;
+	fn claim(p: u32) -> Weight;
+	fn reclaim(c: u32) -> Weight;
+	fn start_destroy() -> Weight;
+	fn destroy_accounts(n: u32) -> Weight;
+	fn destroy_approvals(n: u32) -> Weight;
+	fn destroy_frozen(n: u32) -> Weight;
+	fn finish_destroy() -> Weight;
+	fn freeze() -> Weight;
+	fn thaw() -> Weight;
+	fn set_team() -> Weight;
+	fn set_max_supply() -> Weight;
+}
+
 pub trait Trait: system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// Whether the team's `mint`, `burn` and `burn_from` are still allowed while a token is
+	/// paused. Holders and spenders can never burn a paused token.
+	type AllowMintBurnWhenPaused: Get<bool>;
+
+	/// Whether frozen accounts may still receive tokens.
+	type AllowFrozenReceive: Get<bool>;
+
+	/// Origin allowed to `create` tokens. The account it resolves to is the creator, e.g.
+	/// `EnsureSigned` for permissionless creation or `EnsureSignedBy` to restrict it to the
+	/// operator or the council members.
+	type CreateOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+	/// Origin allowed to `force_create` tokens, e.g. `EnsureRoot` or a council majority.
+	type ForceOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The base amount reserved from the creator of a token.
+	type TokenDeposit: Get<BalanceOf<Self>>;
+
+	/// The additional amount reserved per byte of token metadata (name, symbol, icon URI,
+	/// description and website).
+	type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+
+	/// The maximum length of a token name, in bytes.
+	type MaxNameLength: Get<u32>;
+
+	/// The maximum length of a token symbol, in bytes.
+	type MaxSymbolLength: Get<u32>;
+
+	/// The maximum length of a token's icon URI, in bytes.
+	type MaxIconUriLength: Get<u32>;
+
+	/// The maximum length of a token description, in bytes.
+	type MaxDescriptionLength: Get<u32>;
+
+	/// The maximum length of a token website, in bytes.
+	type MaxWebsiteLength: Get<u32>;
+
+	/// The maximum number of transfers in one `transfer_batch` or `transfer_multi`.
+	type MaxBatchSize: Get<u32>;
+
+	/// Priority of unsigned airdrop claims in the transaction pool.
+	type UnsignedPriority: Get<TransactionPriority>;
+
+	/// The maximum number of hashes in an airdrop claim's merkle proof.
+	type MaxProofLength: Get<u32>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
 }
 
 pub type TokenIndex = u32;
 
 type AccountIdOf<T> = <T as system::Trait>::AccountId;
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<AccountIdOf<T>>>::Balance;
-type TokenInfoOf<T> = TokenInfo<AccountIdOf<T>, <T as system::Trait>::BlockNumber>;
-
-#[derive(Encode, Decode, Default, PartialEq, Eq)]
-#[cfg_attr(feature = "std", derive(Debug))]
-pub struct TokenInfo<AccountId, BlockNumber> {
-	name: Vec<u8>,
-	symbol: Vec<u8>,	
-	owner: AccountId,
-	created: BlockNumber,
+type TokenInfoOf<T> = TokenInfo<BalanceOf<T>, <T as system::Trait>::BlockNumber>;
+
+/// Descriptive information about a token. Ownership lives in the `Owner` map.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct TokenInfo<Balance, BlockNumber> {
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,	
+	/// Number of decimals wallets should use to display balances.
+	pub decimals: u8,
+	/// Cap on the total supply, if any. It can only be lowered after creation.
+	pub max_supply: Option<Balance>,
+	/// Smallest non-zero balance an account may hold. Smaller remainders are swept as dust.
+	pub min_balance: Balance,
+	pub icon_uri: Option<Vec<u8>>,
+	pub description: Vec<u8>,
+	pub website: Vec<u8>,
+	/// Block at which the token was created.
+	pub created: BlockNumber,
+}
+
+impl<Balance, BlockNumber> TokenInfo<Balance, BlockNumber> {
+	/// Number of bytes of variable-length metadata held by the token.
+	fn metadata_len(&self) -> usize {
+		self.name.len()
+			.saturating_add(self.symbol.len())
+			.saturating_add(self.icon_uri.as_ref().map_or(0, |uri| uri.len()))
+			.saturating_add(self.description.len())
+			.saturating_add(self.website.len())
+	}
+}
+
+/// Accounts allowed to manage a token on the owner's behalf.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TokenTeam<AccountId> {
+	/// May mint new tokens.
+	pub issuer: AccountId,
+	/// May burn tokens.
+	pub admin: AccountId,
+	/// May freeze and thaw accounts.
+	pub freezer: AccountId,
+	/// May pause and unpause the token.
+	pub pauser: AccountId,
+}
+
+impl<AccountId: Clone> TokenTeam<AccountId> {
+	/// A team where `who` holds every role.
+	fn all(who: &AccountId) -> Self {
+		TokenTeam { issuer: who.clone(), admin: who.clone(), freezer: who.clone(), pauser: who.clone() }
+	}
+}
+
+type AirdropInfoOf<T> = AirdropInfo<
+	AccountIdOf<T>,
+	BalanceOf<T>,
+	<T as system::Trait>::BlockNumber,
+	<T as system::Trait>::Hash,
+>;
+
+/// Tokens set aside by an issuer for accounts to claim with a merkle proof.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AirdropInfo<AccountId, Balance, BlockNumber, Hash> {
+	pub token: TokenIndex,
+	pub issuer: AccountId,
+	/// Root of the merkle tree over the `(account, amount)` leaves.
+	pub merkle_root: Hash,
+	/// Amount held for claims not made yet. It is not part of any account's balance.
+	pub remaining: Balance,
+	/// Last block at which claims are accepted. The issuer may reclaim the rest afterwards.
+	pub expiry: BlockNumber,
+	/// Number of claims made, each with an entry in `Claimed` until the airdrop is closed.
+	pub claims: u32,
+}
+
+// A value placed in storage that represents the current version of the token storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+enum Releases {
+	#[default]
+	V1,
+	V2,
+	V3,
+	V4,
+	V5,
+	V6,
+	V7,
+	V8,
+	V9,
 }
 
 decl_storage! {
@@ -40,11 +237,89 @@ decl_storage! {
 		pub Tokens get(fn tokens): map hasher(blake2_128_concat) TokenIndex => Option<TokenInfoOf<T>>;
 		pub TokenCount get(fn token_count): TokenIndex;
 
-		pub Balance get(fn balance): map hasher(blake2_128_concat) (u32, T::AccountId) => BalanceOf<T>;
+		/// Token balances, keyed by token then holder so that the holders of one token can be iterated.
+		pub Balance get(fn balance): double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
 		pub Supply get(fn supply): map hasher(blake2_128_concat) u32 => BalanceOf<T>;
 		pub Paused get(fn paused): map hasher(blake2_128_concat) u32 => bool;
-		pub Approval get(fn approval): map hasher(blake2_128_concat) (u32, T::AccountId, T::AccountId) => BalanceOf<T>;
-		pub Owner get(fn owner): map hasher(blake2_128_concat) u32 => T::AccountId;
+		/// Accounts barred from moving a token.
+		pub Frozen get(fn frozen): double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) T::AccountId => bool;
+		/// Allowances, keyed by token then `(owner, spender)`.
+		pub Approval get(fn approval): double_map hasher(blake2_128_concat) u32, hasher(blake2_128_concat) (T::AccountId, T::AccountId) => BalanceOf<T>;
+		pub Owner get(fn owner): map hasher(blake2_128_concat) u32 => Option<T::AccountId>;
+		/// Token using each symbol. Symbols are unique across all tokens.
+		pub SymbolToToken get(fn token_by_symbol): map hasher(blake2_128_concat) Vec<u8> => Option<TokenIndex>;
+		/// Symbols held back by governance; only `force_create` may use them.
+		pub ReservedSymbols get(fn symbol_reserved): map hasher(blake2_128_concat) Vec<u8> => bool;
+
+		/// Account that paid the creation deposit of a token, and the amount reserved.
+		pub Deposit get(fn deposit): map hasher(blake2_128_concat) u32 => Option<(T::AccountId, BalanceOf<T>)>;
+		/// Tokens whose balances may bring an account into existence. Holders of other tokens must
+		/// already have an account.
+		pub IsSufficient get(fn is_sufficient): map hasher(blake2_128_concat) u32 => bool;
+		/// Number of airdrops created, used to index the next one.
+		pub AirdropCount get(fn airdrop_count): u32;
+		/// Open airdrops.
+		pub Airdrops get(fn airdrops): map hasher(twox_64_concat) u32 => Option<AirdropInfoOf<T>>;
+		/// Accounts that have claimed from an airdrop.
+		pub Claimed get(fn claimed): double_map hasher(twox_64_concat) u32, hasher(blake2_128_concat) T::AccountId => bool;
+		/// Tokens frozen by `start_destroy`, whose accounts are being removed.
+		pub Destroying get(fn destroying): map hasher(blake2_128_concat) u32 => bool;
+		/// Role assignments of each token. Removed when ownership is renounced.
+		pub Team get(fn team): map hasher(blake2_128_concat) u32 => Option<TokenTeam<T::AccountId>>;
+		/// Account nominated by the owner to take over the token, pending its acceptance.
+		pub PendingOwner get(fn pending_owner): map hasher(blake2_128_concat) u32 => Option<T::AccountId>;
+
+		/// Storage version of the pallet.
+		///
+		/// This is set to the latest version for new networks.
+		StorageVersion build(|_: &GenesisConfig<T>| Releases::V9): Releases;
+	}
+	add_extra_genesis {
+		/// Tokens to deploy at genesis: `(id, name, symbol, decimals, owner)`.
+		config(tokens): Vec<(TokenIndex, Vec<u8>, Vec<u8>, u8, T::AccountId)>;
+		/// Initial holdings: `(token, holder, balance)`. The supply of each token is their sum.
+		config(balances): Vec<(TokenIndex, T::AccountId, BalanceOf<T>)>;
+		build(|config: &GenesisConfig<T>| {
+			for (id, name, symbol, decimals, owner) in &config.tokens {
+				assert!(!<Tokens<T>>::contains_key(id), "duplicate token id in genesis");
+				<Module<T>>::validate_name(name).expect("invalid token name in genesis");
+				<Module<T>>::validate_symbol(symbol).expect("invalid token symbol in genesis");
+				assert!(!<SymbolToToken>::contains_key(symbol), "duplicate token symbol in genesis");
+
+				<Tokens<T>>::insert(id, TokenInfo {
+					name: name.clone(),
+					symbol: symbol.clone(),
+					decimals: *decimals,
+					max_supply: None,
+					min_balance: Zero::zero(),
+					icon_uri: None,
+					description: Vec::new(),
+					website: Vec::new(),
+					created: Default::default(),
+				});
+				<SymbolToToken>::insert(symbol, id);
+				<Owner<T>>::insert(id, owner);
+				<Team<T>>::insert(id, TokenTeam::all(owner));
+				if *id >= TokenCount::get() {
+					TokenCount::put((*id).checked_add(1).expect("token id overflow in genesis"));
+				}
+			}
+
+			for (token, who, balance) in &config.balances {
+				assert!(<Tokens<T>>::contains_key(token), "genesis balance for an unknown token");
+				let supply = <Module<T>>::supply(token)
+					.checked_add(balance)
+					.expect("token supply overflow in genesis");
+				<Supply<T>>::insert(token, supply);
+				<Module<T>>::set_balance(*token, who, *balance);
+			}
+
+			for (id, ..) in &config.tokens {
+				let held = <Balance<T>>::iter_prefix_values(id)
+					.fold(BalanceOf::<T>::default(), |total, balance| total.saturating_add(balance));
+				assert!(<Supply<T>>::get(id) == held, "token supply does not match the sum of balances in genesis; is a holder listed twice?");
+			}
+		})
 	}
 }
 
@@ -56,18 +331,56 @@ decl_event!(
 	{
 		/// A token was created by user. \[token_id, owner_id\]
 		Created(u32, AccountId),
-		/// Token burned. \[token, sender, amount\]
-		Burn(u32, AccountId, Balance),
-		/// Token minted. \[token, receiver, amount\]
-		Mint(u32, AccountId, Balance),
+		/// Token burned. \[token, owner, burner, amount\]
+		Burn(u32, AccountId, AccountId, Balance),
+		/// Token minted. \[token, beneficiary, issuer, amount\]
+		Mint(u32, AccountId, AccountId, Balance),
 		/// Token transferred. \[token, sender, receiver, amount\]
 		Transfer(u32, AccountId, AccountId, Balance),
-		/// Token transferred. \[token, sender, spender, amount\]
+		/// Token transferred by an approved spender. \[token, sender, spender, amount\]
 		TransferFrom(u32, AccountId, AccountId, Balance),		
-		/// Token approved. \[token, spender, user, amount\]
+		/// Token approved. \[token, owner, spender, amount\]
 		Approval(u32, AccountId, AccountId, Balance),
 		/// Token paused/unpaused. \[token, status\]
 		PausedOperation(u32, bool),
+		/// Token ownership transfer was proposed. \[token, owner, new_owner\]
+		OwnershipTransferProposed(u32, AccountId, AccountId),
+		/// Token ownership was accepted by the new owner. \[token, old_owner, new_owner\]
+		OwnershipTransferred(u32, AccountId, AccountId),
+		/// Token ownership was given up; the token has no owner anymore. \[token, old_owner\]
+		OwnershipRenounced(u32, AccountId),
+		/// Token icon URI, description or website changed. \[token\]
+		MetadataUpdated(u32),
+		/// A symbol was reserved by governance. \[symbol\]
+		SymbolReserved(Vec<u8>),
+		/// A symbol reservation was lifted. \[symbol\]
+		SymbolReleased(Vec<u8>),
+		/// Whether a token is sufficient to keep an account alive was changed. \[token, is_sufficient\]
+		SufficiencyChanged(u32, bool),
+		/// An airdrop was funded. \[airdrop, token, issuer, amount\]
+		AirdropCreated(u32, u32, AccountId, Balance),
+		/// An airdrop was claimed. \[airdrop, who, amount\]
+		AirdropClaimed(u32, AccountId, Balance),
+		/// An airdrop was closed and its unclaimed rest returned to its issuer. \[airdrop, amount\]
+		AirdropReclaimed(u32, Balance),
+		/// Token destruction started; the token is frozen. \[token\]
+		DestructionStarted(u32),
+		/// Holder accounts of a token being destroyed were removed. \[token, removed\]
+		AccountsDestroyed(u32, u32),
+		/// Approvals of a token being destroyed were removed. \[token, removed\]
+		ApprovalsDestroyed(u32, u32),
+		/// Frozen marks of a token being destroyed were removed. \[token, removed\]
+		FrozenDestroyed(u32, u32),
+		/// Token destroyed and its storage removed. \[token\]
+		Destroyed(u32),
+		/// An account was frozen. \[token, who\]
+		Frozen(u32, AccountId),
+		/// An account was thawed. \[token, who\]
+		Thawed(u32, AccountId),
+		/// Token roles were reassigned. \[token, issuer, admin, freezer, pauser\]
+		TeamChanged(u32, AccountId, AccountId, AccountId, AccountId),
+		/// The supply cap of a token was lowered. \[token, max_supply\]
+		MaxSupplyLowered(u32, Balance),
 	}
 );
 
@@ -76,6 +389,80 @@ decl_error! {
 		NotTokenOwner,
 		InsufficientAmount,
 		InsufficientApproval,
+		/// The account does not hold enough of the token.
+		InsufficientBalance,
+		/// A balance or the token supply would overflow.
+		Overflow,
+		/// No token exists with the given index.
+		TokenNotFound,
+		/// The token is paused.
+		TokenPaused,
+		/// The caller is not the pending owner of the token.
+		NotPendingOwner,
+		/// The token name is longer than `MaxNameLength`.
+		NameTooLong,
+		/// The token symbol is longer than `MaxSymbolLength`.
+		SymbolTooLong,
+		/// The token name is empty or not valid UTF-8.
+		InvalidName,
+		/// The token symbol is empty or contains characters other than `A-Z` and `0-9`.
+		InvalidSymbol,
+		/// Another token already uses this symbol.
+		SymbolTaken,
+		/// The symbol is reserved and can only be used through `force_create`.
+		SymbolReserved,
+		/// The symbol is not reserved.
+		SymbolNotReserved,
+		/// The token is being destroyed.
+		TokenDestroying,
+		/// The token is not being destroyed; call `start_destroy` first.
+		NotDestroying,
+		/// Holder accounts remain; call `destroy_accounts` until none are left.
+		AccountsRemaining,
+		/// Approvals remain; call `destroy_approvals` until none are left.
+		ApprovalsRemaining,
+		/// Frozen marks remain; call `destroy_frozen` until none are left.
+		FrozenRemaining,
+		/// The account is frozen for this token.
+		AccountFrozen,
+		/// The caller does not hold the role required for this operation.
+		NoPermission,
+		/// The total supply would exceed the token's cap.
+		MaxSupplyExceeded,
+		/// The cap can only be lowered, and not below the current supply.
+		InvalidMaxSupply,
+		/// The resulting balance would be non-zero but below the token's minimum balance.
+		BelowMinBalance,
+		/// The transfer would leave the sender below the minimum balance.
+		WouldLeaveDust,
+		/// The receiver has no account and the token is not sufficient to create one.
+		NoAccount,
+		/// More transfers than `MaxBatchSize` were given.
+		BatchTooLarge,
+		/// No airdrop with this index is open.
+		AirdropNotFound,
+		/// The airdrop no longer accepts claims.
+		AirdropExpired,
+		/// The airdrop still accepts claims.
+		AirdropNotExpired,
+		/// The account has already claimed from this airdrop.
+		AlreadyClaimed,
+		/// The proof does not lead to the airdrop's merkle root.
+		InvalidProof,
+		/// The airdrop holds less than the amount claimed.
+		AirdropExhausted,
+		/// The number of claims given is less than the number made.
+		BadWitness,
+		/// Some accounts are frozen for this token.
+		AccountsFrozen,
+		/// The icon URI is longer than `MaxIconUriLength`.
+		IconUriTooLong,
+		/// The description is longer than `MaxDescriptionLength`.
+		DescriptionTooLong,
+		/// The website is longer than `MaxWebsiteLength`.
+		WebsiteTooLong,
+		/// The merkle proof is longer than `MaxProofLength`.
+		ProofTooLong,
 	}
 }
 
@@ -83,143 +470,1006 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate::<T>()
+		}
 
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::create(name.len() as u32, symbol.len() as u32)]
 		pub fn create(origin, 
 			owner:AccountIdOf<T>, 
 			name:Vec<u8>, 
 			symbol: Vec<u8>, 
-			initial_supply: BalanceOf<T>
+			decimals: u8, 
+			initial_supply: BalanceOf<T>, 
+			max_supply: Option<BalanceOf<T>>, 
+			min_balance: BalanceOf<T>
 		) -> DispatchResult {
+			let creator = T::CreateOrigin::ensure_origin(origin)?;
+			ensure!(!Self::symbol_reserved(&symbol), <Error<T>>::SymbolReserved);
+			Self::create_(owner, name, symbol, decimals, initial_supply, max_supply, min_balance, Some(creator))?;
+			Ok(())
+		}	
 
-			let caller = ensure_signed(origin)?;
-
-			let index = TokenCount::get();
-			TokenCount::put(index + 1);		
-			
-			let created = <system::Module<T>>::block_number();
-
-			<Tokens<T>>::insert(index, TokenInfo {
-				name,
-				symbol,
-				owner,
-				created
-			});			
-
-			<Balance<T>>::insert((index, &caller), initial_supply);
-			<Supply<T>>::insert(index, initial_supply);
-			<Owner<T>>::insert(index, &caller);
-
-			Self::deposit_event(RawEvent::Created(index, caller));
-
+		#[weight = T::WeightInfo::force_create(name.len() as u32, symbol.len() as u32)]
+		pub fn force_create(origin, 
+			owner:AccountIdOf<T>, 
+			name:Vec<u8>, 
+			symbol: Vec<u8>, 
+			decimals: u8, 
+			initial_supply: BalanceOf<T>, 
+			max_supply: Option<BalanceOf<T>>, 
+			min_balance: BalanceOf<T>
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			Self::create_(owner, name, symbol, decimals, initial_supply, max_supply, min_balance, None)?;
 			Ok(())
 		}	
 		
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::transfer()]
 		pub fn transfer(origin, 
 			token:u32, 
 			to: T::AccountId, 
 			value: BalanceOf<T> 
 		) -> DispatchResult {
 			let caller = ensure_signed(origin)?;
-			Self::transfer_(token, caller, to, value);
-			Ok(())
+			Self::transfer_(token, caller, to, value, false)
+		}	
+
+		/// Like `transfer`, but fails rather than leave the caller with a dust balance.
+		#[weight = T::WeightInfo::transfer_keep_alive()]
+		pub fn transfer_keep_alive(origin, 
+			token: u32, 
+			to: T::AccountId, 
+			value: BalanceOf<T> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::transfer_(token, caller, to, value, true)
+		}	
+
+		/// Make several transfers of one token from the caller. Either all of them succeed or
+		/// none is applied.
+		#[weight = T::WeightInfo::transfer_batch(transfers.len() as u32)]
+		pub fn transfer_batch(origin, 
+			token: u32, 
+			transfers: Vec<(T::AccountId, BalanceOf<T>)> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(transfers.len() <= T::MaxBatchSize::get() as usize, <Error<T>>::BatchTooLarge);
+
+			let legs = transfers.into_iter().map(|(to, value)| (token, to, value)).collect();
+			Self::transfer_all(caller, legs)
+		}	
+
+		/// Make transfers of several tokens from the caller, e.g. a payment and its fee. Either all
+		/// of them succeed or none is applied.
+		#[weight = T::WeightInfo::transfer_multi(transfers.len() as u32)]
+		pub fn transfer_multi(origin, 
+			transfers: Vec<(TokenIndex, T::AccountId, BalanceOf<T>)> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(transfers.len() <= T::MaxBatchSize::get() as usize, <Error<T>>::BatchTooLarge);
+
+			Self::transfer_all(caller, transfers)
 		}	
 		
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::transfer_from()]
 		pub fn transfer_from(origin, 
 			token:u32, 
 			from: T::AccountId, 
+			to: T::AccountId, 
 			value: BalanceOf<T> 
 		) -> DispatchResult {
-			let to = ensure_signed(origin)?;
-			Self::transfer_(token, from, to, value);
+			let spender = ensure_signed(origin)?;
+			let allowance = Self::approval(token, (&from, &spender))
+				.checked_sub(&value)
+				.ok_or(<Error<T>>::InsufficientApproval)?;
+
+			Self::transfer_(token, from.clone(), to, value, true)?;
+			<Approval<T>>::insert(token, (&from, &spender), allowance);
+
+			Self::deposit_event(RawEvent::TransferFrom(token, from, spender, value));
 			Ok(())
 		}			
 
+		#[weight = T::WeightInfo::approve()]
+		pub fn approve(origin, 
+			token:u32, 
+			spender: T::AccountId, 
+			value: BalanceOf<T> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::approve_(token, caller, spender, value)
+		}	
+
+		#[weight = T::WeightInfo::increase_allowance()]
+		pub fn increase_allowance(origin, 
+			token:u32, 
+			spender: T::AccountId, 
+			value: BalanceOf<T> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let allowance = Self::approval(token, (&caller, &spender))
+				.checked_add(&value)
+				.ok_or(<Error<T>>::Overflow)?;
+			Self::approve_(token, caller, spender, allowance)
+		}	
+
+		#[weight = T::WeightInfo::decrease_allowance()]
+		pub fn decrease_allowance(origin, 
+			token:u32, 
+			spender: T::AccountId, 
+			value: BalanceOf<T> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let allowance = Self::approval(token, (&caller, &spender))
+				.checked_sub(&value)
+				.ok_or(<Error<T>>::InsufficientApproval)?;
+			Self::approve_(token, caller, spender, allowance)
+		}	
+
 		
-		#[weight = 10_000]
+		#[weight = T::WeightInfo::pause()]
 		pub fn pause(origin, 
 			token: u32, 
 			status: bool 
 		) -> DispatchResult {
 			let caller = ensure_signed(origin)?;
-			let token_owner = Self::owner(token);
-			ensure!(caller == token_owner, <Error<T>>::NotTokenOwner);
+			Self::ensure_role(token, &caller, |team| &team.pauser)?;
 
-			let token_boolean = Self::paused(token);
-			let new_status: bool;
-			if token_boolean {
-				new_status = true;
-			} else {	
-				new_status = false;			
-			}
-			<Paused>::insert(token, new_status);			
-			Self::deposit_event(RawEvent::PausedOperation(token, new_status));
-			Ok(())
+			Self::set_paused(token, status)
+		}	
+
+		#[weight = T::WeightInfo::unpause()]
+		pub fn unpause(origin, 
+			token: u32 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_role(token, &caller, |team| &team.pauser)?;
+			Self::set_paused(token, false)
 		}	
 		
-		#[weight = 10_000]
+		/// Mint `value` new tokens to `beneficiary`. Only the issuer may do this.
+		#[weight = T::WeightInfo::mint()]
 		pub fn mint(origin, 
 			token:u32, 
+			beneficiary: T::AccountId, 
 			value: BalanceOf<T> 
 		) -> DispatchResult {
 			let caller = ensure_signed(origin)?;
-			let token_owner = Self::owner(token);
-			ensure!(caller == token_owner, <Error<T>>::NotTokenOwner);			
-			Self::mint_(caller, token, value);
-			Ok(())
+			Self::ensure_role(token, &caller, |team| &team.issuer)?;
+			Self::mint_(token, beneficiary, caller, value)
 		}	
 		
-		#[weight = 10_000]
+		/// Burn `value` tokens from the admin's own balance.
+		#[weight = T::WeightInfo::burn()]
 		pub fn burn(origin, 
 			token:u32, 
 			value: BalanceOf<T> 
 		) -> DispatchResult {
 			let caller = ensure_signed(origin)?;
-			let token_owner = Self::owner(token);
-			ensure!(caller == token_owner, <Error<T>>::NotTokenOwner);			
-			Self::burn_(caller, token, value);
+			Self::ensure_role(token, &caller, |team| &team.admin)?;
+			Self::burn_(token, caller.clone(), caller, value, false)
+		}	
+
+		/// Burn `value` tokens held by `who`, e.g. on redemption. Only the admin may do this.
+		#[weight = T::WeightInfo::burn_from()]
+		pub fn burn_from(origin, 
+			token: u32, 
+			who: T::AccountId, 
+			value: BalanceOf<T> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_role(token, &caller, |team| &team.admin)?;
+			Self::burn_(token, who, caller, value, false)
+		}	
+
+		/// Burn `value` tokens from the caller's own balance.
+		#[weight = T::WeightInfo::burn_own()]
+		pub fn burn_own(origin, 
+			token: u32, 
+			value: BalanceOf<T> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(!Self::paused(token), <Error<T>>::TokenPaused);
+			ensure!(!Self::frozen(token, &caller), <Error<T>>::AccountFrozen);
+			Self::burn_(token, caller.clone(), caller, value, false)
+		}	
+
+		/// Burn `value` tokens held by `from`, spending the caller's allowance. Fails rather than
+		/// burn a remainder below the minimum balance that the allowance does not cover.
+		#[weight = T::WeightInfo::burn_with_allowance()]
+		pub fn burn_with_allowance(origin, 
+			token: u32, 
+			from: T::AccountId, 
+			value: BalanceOf<T> 
+		) -> DispatchResult {
+			let spender = ensure_signed(origin)?;
+			ensure!(!Self::paused(token), <Error<T>>::TokenPaused);
+			ensure!(!Self::frozen(token, &from), <Error<T>>::AccountFrozen);
+			let allowance = Self::approval(token, (&from, &spender))
+				.checked_sub(&value)
+				.ok_or(<Error<T>>::InsufficientApproval)?;
+
+			Self::burn_(token, from.clone(), spender.clone(), value, true)?;
+			<Approval<T>>::insert(token, (&from, &spender), allowance);
 			Ok(())
 		}	
 
 	
+
+		/// Bar `who` from sending the token.
+		#[weight = T::WeightInfo::freeze()]
+		pub fn freeze(origin, 
+			token: u32, 
+			who: T::AccountId 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_role(token, &caller, |team| &team.freezer)?;
+
+			<Frozen<T>>::insert(token, &who, true);
+
+			Self::deposit_event(RawEvent::Frozen(token, who));
+			Ok(())
+		}	
+
+		/// Allow a frozen account to send the token again.
+		#[weight = T::WeightInfo::thaw()]
+		pub fn thaw(origin, 
+			token: u32, 
+			who: T::AccountId 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_role(token, &caller, |team| &team.freezer)?;
+
+			<Frozen<T>>::remove(token, &who);
+
+			Self::deposit_event(RawEvent::Thawed(token, who));
+			Ok(())
+		}	
+
+		/// Reassign the roles of a token. Only the owner may do this.
+		#[weight = T::WeightInfo::set_team()]
+		pub fn set_team(origin, 
+			token: u32, 
+			issuer: T::AccountId, 
+			admin: T::AccountId, 
+			freezer: T::AccountId, 
+			pauser: T::AccountId 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_owner(token, &caller)?;
+
+			<Team<T>>::insert(token, TokenTeam {
+				issuer: issuer.clone(),
+				admin: admin.clone(),
+				freezer: freezer.clone(),
+				pauser: pauser.clone(),
+			});
+
+			Self::deposit_event(RawEvent::TeamChanged(token, issuer, admin, freezer, pauser));
+			Ok(())
+		}	
+
+		/// Set or lower the supply cap of a token. It must stay at or above the current supply.
+		#[weight = T::WeightInfo::set_max_supply()]
+		pub fn set_max_supply(origin, 
+			token: u32, 
+			max_supply: BalanceOf<T> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_owner(token, &caller)?;
+
+			let mut info = Self::tokens(token).ok_or(<Error<T>>::TokenNotFound)?;
+			ensure!(info.max_supply.is_none_or(|max| max_supply <= max), <Error<T>>::InvalidMaxSupply);
+			ensure!(Self::supply(token) <= max_supply, <Error<T>>::InvalidMaxSupply);
+			info.max_supply = Some(max_supply);
+			<Tokens<T>>::insert(token, info);
+
+			Self::deposit_event(RawEvent::MaxSupplyLowered(token, max_supply));
+			Ok(())
+		}	
+
+		#[weight = T::WeightInfo::transfer_ownership()]
+		pub fn transfer_ownership(origin, 
+			token: u32, 
+			new_owner: T::AccountId 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_owner(token, &caller)?;
+
+			<PendingOwner<T>>::insert(token, &new_owner);
+
+			Self::deposit_event(RawEvent::OwnershipTransferProposed(token, caller, new_owner));
+			Ok(())
+		}	
+
+		/// Become the owner of a token whose transfer was proposed to the caller. The caller takes
+		/// over every team role.
+		#[weight = T::WeightInfo::accept_ownership()]
+		pub fn accept_ownership(origin, 
+			token: u32 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(Self::pending_owner(token).as_ref() == Some(&caller), <Error<T>>::NotPendingOwner);
+			let old_owner = Self::owner(token).ok_or(<Error<T>>::NotTokenOwner)?;
+
+			<Owner<T>>::insert(token, &caller);
+			<PendingOwner<T>>::remove(token);
+			<Team<T>>::insert(token, TokenTeam::all(&caller));
+
+			Self::deposit_event(RawEvent::TeamChanged(token, caller.clone(), caller.clone(), caller.clone(), caller.clone()));
+			Self::deposit_event(RawEvent::OwnershipTransferred(token, old_owner, caller));
+			Ok(())
+		}	
+
+		#[weight = T::WeightInfo::renounce_ownership()]
+		pub fn renounce_ownership(origin, 
+			token: u32 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_owner(token, &caller)?;
+			// Without a team nobody could unpause or thaw again.
+			ensure!(!Self::paused(token), <Error<T>>::TokenPaused);
+			ensure!(<Frozen<T>>::iter_prefix(token).next().is_none(), <Error<T>>::AccountsFrozen);
+
+			<Owner<T>>::remove(token);
+			<PendingOwner<T>>::remove(token);
+			<Team<T>>::remove(token);
+
+			Self::deposit_event(RawEvent::OwnershipRenounced(token, caller));
+			Ok(())
+		}	
+
+		#[weight = T::WeightInfo::set_metadata(
+			icon_uri.as_ref().map_or(0, |uri| uri.len())
+				.saturating_add(description.len())
+				.saturating_add(website.len()) as u32
+		)]
+		pub fn set_metadata(origin, 
+			token: u32, 
+			icon_uri: Option<Vec<u8>>, 
+			description: Vec<u8>, 
+			website: Vec<u8> 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_owner(token, &caller)?;
+			ensure!(
+				icon_uri.as_ref().is_none_or(|uri| uri.len() <= T::MaxIconUriLength::get() as usize),
+				<Error<T>>::IconUriTooLong
+			);
+			ensure!(description.len() <= T::MaxDescriptionLength::get() as usize, <Error<T>>::DescriptionTooLong);
+			ensure!(website.len() <= T::MaxWebsiteLength::get() as usize, <Error<T>>::WebsiteTooLong);
+
+			let mut info = Self::tokens(token).ok_or(<Error<T>>::TokenNotFound)?;
+			info.icon_uri = icon_uri;
+			info.description = description;
+			info.website = website;
+
+			// The caller takes over the deposit from whoever placed it.
+			if let Some((depositor, old_deposit)) = Self::deposit(token) {
+				let new_deposit = Self::metadata_deposit(&info);
+				if depositor != caller {
+					T::Currency::reserve(&caller, new_deposit)?;
+					T::Currency::unreserve(&depositor, old_deposit);
+				} else if new_deposit > old_deposit {
+					T::Currency::reserve(&caller, new_deposit - old_deposit)?;
+				} else {
+					T::Currency::unreserve(&caller, old_deposit - new_deposit);
+				}
+				<Deposit<T>>::insert(token, (caller, new_deposit));
+			}
+
+			<Tokens<T>>::insert(token, info);
+
+			Self::deposit_event(RawEvent::MetadataUpdated(token));
+			Ok(())
+		}	
+
+		#[weight = T::WeightInfo::reserve_symbol(symbol.len() as u32)]
+		pub fn reserve_symbol(origin, 
+			symbol: Vec<u8> 
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			Self::validate_symbol(&symbol)?;
+			ensure!(!<SymbolToToken>::contains_key(&symbol), <Error<T>>::SymbolTaken);
+
+			<ReservedSymbols>::insert(&symbol, true);
+
+			Self::deposit_event(RawEvent::SymbolReserved(symbol));
+			Ok(())
+		}	
+
+		#[weight = T::WeightInfo::release_symbol(symbol.len() as u32)]
+		pub fn release_symbol(origin, 
+			symbol: Vec<u8> 
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(Self::symbol_reserved(&symbol), <Error<T>>::SymbolNotReserved);
+
+			<ReservedSymbols>::remove(&symbol);
+
+			Self::deposit_event(RawEvent::SymbolReleased(symbol));
+			Ok(())
+		}	
+
+		/// Set whether holding the token is enough for an account to exist.
+		#[weight = T::WeightInfo::set_sufficient()]
+		pub fn set_sufficient(origin, 
+			token: u32, 
+			is_sufficient: bool 
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(<Tokens<T>>::contains_key(token), <Error<T>>::TokenNotFound);
+
+			<IsSufficient>::insert(token, is_sufficient);
+
+			Self::deposit_event(RawEvent::SufficiencyChanged(token, is_sufficient));
+			Ok(())
+		}	
+
+		/// Freeze a token so that it can be destroyed. Callable by the owner or `ForceOrigin`.
+		#[weight = T::WeightInfo::start_destroy()]
+		pub fn start_destroy(origin, 
+			token: u32 
+		) -> DispatchResult {
+			if let Err(origin) = T::ForceOrigin::try_origin(origin) {
+				let caller = ensure_signed(origin)?;
+				Self::ensure_owner(token, &caller)?;
+			}
+			Self::ensure_live(token)?;
+
+			<Destroying>::insert(token, true);
+
+			Self::deposit_event(RawEvent::DestructionStarted(token));
+			Ok(())
+		}	
+
+		/// Remove up to `max` holder accounts of a token being destroyed.
+		#[weight = T::WeightInfo::destroy_accounts(*max)]
+		pub fn destroy_accounts(origin, 
+			token: u32, 
+			max: u32 
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(Self::destroying(token), <Error<T>>::NotDestroying);
+
+			let holders = <Balance<T>>::iter_prefix(token)
+				.take(max as usize)
+				.collect::<Vec<_>>();
+			let mut supply = Self::supply(token);
+			for (who, balance) in &holders {
+				supply = supply.saturating_sub(*balance);
+				Self::set_balance(token, who, Zero::zero());
+				<Frozen<T>>::remove(token, who);
+			}
+			<Supply<T>>::insert(token, supply);
+
+			Self::deposit_event(RawEvent::AccountsDestroyed(token, holders.len() as u32));
+			Ok(())
+		}	
+
+		/// Remove up to `max` approvals of a token being destroyed.
+		#[weight = T::WeightInfo::destroy_approvals(*max)]
+		pub fn destroy_approvals(origin, 
+			token: u32, 
+			max: u32 
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(Self::destroying(token), <Error<T>>::NotDestroying);
+
+			let approvals = <Approval<T>>::iter_prefix(token)
+				.take(max as usize)
+				.map(|(key, _)| key)
+				.collect::<Vec<_>>();
+			for key in &approvals {
+				<Approval<T>>::remove(token, key);
+			}
+
+			Self::deposit_event(RawEvent::ApprovalsDestroyed(token, approvals.len() as u32));
+			Ok(())
+		}	
+
+		/// Remove up to `max` frozen marks of a token being destroyed, such as those left on
+		/// accounts holding none of it.
+		#[weight = T::WeightInfo::destroy_frozen(*max)]
+		pub fn destroy_frozen(origin, 
+			token: u32, 
+			max: u32 
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(Self::destroying(token), <Error<T>>::NotDestroying);
+
+			let frozen = <Frozen<T>>::iter_prefix(token)
+				.take(max as usize)
+				.map(|(who, _)| who)
+				.collect::<Vec<_>>();
+			for who in &frozen {
+				<Frozen<T>>::remove(token, who);
+			}
+
+			Self::deposit_event(RawEvent::FrozenDestroyed(token, frozen.len() as u32));
+			Ok(())
+		}	
+
+		/// Remove what is left of a token once its accounts, approvals and frozen marks are gone,
+		/// and return the creation deposit.
+		#[weight = T::WeightInfo::finish_destroy()]
+		pub fn finish_destroy(origin, 
+			token: u32 
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(Self::destroying(token), <Error<T>>::NotDestroying);
+			ensure!(<Balance<T>>::iter_prefix(token).next().is_none(), <Error<T>>::AccountsRemaining);
+			ensure!(<Approval<T>>::iter_prefix(token).next().is_none(), <Error<T>>::ApprovalsRemaining);
+			ensure!(<Frozen<T>>::iter_prefix(token).next().is_none(), <Error<T>>::FrozenRemaining);
+
+			if let Some(info) = <Tokens<T>>::take(token) {
+				<SymbolToToken>::remove(&info.symbol);
+			}
+			if let Some((depositor, deposit)) = <Deposit<T>>::take(token) {
+				T::Currency::unreserve(&depositor, deposit);
+			}
+			<Supply<T>>::remove(token);
+			<Paused>::remove(token);
+			<Owner<T>>::remove(token);
+			<PendingOwner<T>>::remove(token);
+			<Team<T>>::remove(token);
+			<Destroying>::remove(token);
+			<IsSufficient>::remove(token);
+
+			Self::deposit_event(RawEvent::Destroyed(token));
+			Ok(())
+		}	
+
+		/// Set `amount` of the caller's tokens aside for the accounts in the merkle tree with root
+		/// `merkle_root`, each leaf being an `(account, amount)` pair. Only the issuer may do this.
+		#[weight = T::WeightInfo::create_airdrop()]
+		pub fn create_airdrop(origin, 
+			token: u32, 
+			merkle_root: T::Hash, 
+			amount: BalanceOf<T>, 
+			expiry: T::BlockNumber 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			Self::ensure_role(token, &caller, |team| &team.issuer)?;
+			Self::ensure_live(token)?;
+			ensure!(!Self::paused(token), <Error<T>>::TokenPaused);
+			ensure!(!Self::frozen(token, &caller), <Error<T>>::AccountFrozen);
+			ensure!(expiry > <system::Module<T>>::block_number(), <Error<T>>::AirdropExpired);
+
+			let issuer_balance = Self::balance(token, &caller)
+				.checked_sub(&amount)
+				.ok_or(<Error<T>>::InsufficientBalance)?;
+			ensure!(
+				issuer_balance.is_zero() || issuer_balance >= Self::min_balance(token),
+				<Error<T>>::WouldLeaveDust
+			);
+			let index = Self::airdrop_count();
+			let next_index = index.checked_add(1).ok_or(<Error<T>>::Overflow)?;
+
+			Self::set_balance(token, &caller, issuer_balance);
+			<Airdrops<T>>::insert(index, AirdropInfo {
+				token,
+				issuer: caller.clone(),
+				merkle_root,
+				remaining: amount,
+				expiry,
+				claims: 0,
+			});
+			AirdropCount::put(next_index);
+
+			Self::deposit_event(RawEvent::AirdropCreated(index, token, caller, amount));
+			Ok(())
+		}	
+
+		/// Claim `amount` from an airdrop for `who`. Submitted unsigned, so that claimants need no
+		/// funds; see `validate_unsigned`.
+		#[weight = T::WeightInfo::claim(proof.len() as u32)]
+		pub fn claim(origin, 
+			airdrop: u32, 
+			who: T::AccountId, 
+			amount: BalanceOf<T>, 
+			proof: Vec<T::Hash> 
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let (mut info, balance) = Self::check_claim(airdrop, &who, amount, &proof)?;
+			let token = info.token;
+
+			info.remaining = info.remaining.saturating_sub(amount);
+			info.claims = info.claims.saturating_add(1);
+			<Airdrops<T>>::insert(airdrop, info);
+			<Claimed<T>>::insert(airdrop, &who, true);
+			Self::set_balance(token, &who, balance);
+
+			Self::deposit_event(RawEvent::AirdropClaimed(airdrop, who, amount));
+			Ok(())
+		}	
+
+		/// Close an expired airdrop and return what was not claimed to its issuer. A remainder
+		/// that would leave the issuer with dust is burned instead. An airdrop whose token is
+		/// destroyed or being destroyed can be closed at any time and returns nothing.
+		///
+		/// `claims` must be at least the number of claims made, which bounds the `Claimed`
+		/// entries removed.
+		#[weight = T::WeightInfo::reclaim(*claims)]
+		pub fn reclaim(origin, 
+			airdrop: u32, 
+			claims: u32 
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let info = Self::airdrops(airdrop).ok_or(<Error<T>>::AirdropNotFound)?;
+			ensure!(info.issuer == caller, <Error<T>>::NoPermission);
+			ensure!(info.claims <= claims, <Error<T>>::BadWitness);
+			let token = info.token;
+
+			let returned = if Self::ensure_live(token).is_ok() {
+				ensure!(<system::Module<T>>::block_number() > info.expiry, <Error<T>>::AirdropNotExpired);
+				let balance = Self::balance(token, &caller)
+					.checked_add(&info.remaining)
+					.ok_or(<Error<T>>::Overflow)?;
+				if balance >= Self::min_balance(token) {
+					Self::set_balance(token, &caller, balance);
+				} else {
+					<Supply<T>>::mutate(token, |supply| *supply = supply.saturating_sub(info.remaining));
+				}
+				info.remaining
+			} else {
+				if Self::destroying(token) {
+					<Supply<T>>::mutate(token, |supply| *supply = supply.saturating_sub(info.remaining));
+				}
+				Zero::zero()
+			};
+			<Airdrops<T>>::remove(airdrop);
+			<Claimed<T>>::remove_prefix(airdrop);
+
+			Self::deposit_event(RawEvent::AirdropReclaimed(airdrop, returned));
+			Ok(())
+		}	
 	}
 }
 
 impl<T: Trait> Module<T> {
 
-	pub fn transfer_(token: u32, from: AccountIdOf<T>, to: AccountIdOf<T>, value: BalanceOf<T> ) -> () {
-		let from_balance = Self::balance((token, &from));
-		let to_balance = Self::balance((token, &to));
+	pub fn create_(
+		owner: AccountIdOf<T>,
+		name: Vec<u8>,
+		symbol: Vec<u8>,
+		decimals: u8,
+		initial_supply: BalanceOf<T>,
+		max_supply: Option<BalanceOf<T>>,
+		min_balance: BalanceOf<T>,
+		depositor: Option<AccountIdOf<T>>,
+	) -> Result<TokenIndex, DispatchError> {
+		Self::validate_name(&name)?;
+		Self::validate_symbol(&symbol)?;
+		ensure!(!<SymbolToToken>::contains_key(&symbol), <Error<T>>::SymbolTaken);
+		ensure!(max_supply.is_none_or(|max| initial_supply <= max), <Error<T>>::MaxSupplyExceeded);
+		ensure!(initial_supply.is_zero() || initial_supply >= min_balance, <Error<T>>::BelowMinBalance);
+
+		let index = TokenCount::get();
+		let next_index = index.checked_add(1).ok_or(<Error<T>>::Overflow)?;
+
+		let info = TokenInfo {
+			name,
+			symbol,
+			decimals,
+			max_supply,
+			min_balance,
+			icon_uri: None,
+			description: Vec::new(),
+			website: Vec::new(),
+			created: <system::Module<T>>::block_number(),
+		};
+
+		if let Some(depositor) = depositor {
+			let deposit = Self::metadata_deposit(&info);
+			T::Currency::reserve(&depositor, deposit)?;
+			<Deposit<T>>::insert(index, (depositor, deposit));
+		}
+
+		TokenCount::put(next_index);
+		<ReservedSymbols>::remove(&info.symbol);
+		<SymbolToToken>::insert(&info.symbol, index);
+		<Tokens<T>>::insert(index, info);
+
+		Self::set_balance(index, &owner, initial_supply);
+		<Supply<T>>::insert(index, initial_supply);
+		<Owner<T>>::insert(index, &owner);
+		<Team<T>>::insert(index, TokenTeam::all(&owner));
+
+		Self::deposit_event(RawEvent::Created(index, owner));
+		Ok(index)
+	}
+
+	pub fn validate_name(name: &[u8]) -> DispatchResult {
+		ensure!(name.len() <= T::MaxNameLength::get() as usize, <Error<T>>::NameTooLong);
+		ensure!(!name.is_empty() && sp_std::str::from_utf8(name).is_ok(), <Error<T>>::InvalidName);
+		Ok(())
+	}
+
+	pub fn validate_symbol(symbol: &[u8]) -> DispatchResult {
+		ensure!(symbol.len() <= T::MaxSymbolLength::get() as usize, <Error<T>>::SymbolTooLong);
+		ensure!(
+			!symbol.is_empty() && symbol.iter().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()),
+			<Error<T>>::InvalidSymbol
+		);
+		Ok(())
+	}
+
+	/// The amount reserved for a token holding the given metadata.
+	pub fn metadata_deposit(info: &TokenInfoOf<T>) -> BalanceOf<T> {
+		let bytes = info.metadata_len() as u32;
+		T::MetadataDepositPerByte::get()
+			.saturating_mul(bytes.into())
+			.saturating_add(T::TokenDeposit::get())
+	}
 
-		<Balance<T>>::insert((token, &from), from_balance - value);
-		<Balance<T>>::insert((token, &to), to_balance + value);
-		Self::deposit_event(RawEvent::Transfer(token, from, to, value));
+	/// Ensure the token exists and is not being destroyed.
+	pub fn ensure_live(token: u32) -> Result<(), Error<T>> {
+		ensure!(<Tokens<T>>::contains_key(token), <Error<T>>::TokenNotFound);
+		ensure!(!Self::destroying(token), <Error<T>>::TokenDestroying);
+		Ok(())
 	}
 
-	pub fn mint_(minter: AccountIdOf<T>, token: u32, value: BalanceOf<T>) -> () {
-		let minter_balance = Self::balance((token, &minter));
-		let token_supply = Self::supply(token);
-		<Balance<T>>::insert((token, &minter), minter_balance + value);
-		<Supply<T>>::insert(token, token_supply + value);
+	/// Ensure `who` holds the role picked out of the token's team by `role`.
+	pub fn ensure_role(
+		token: u32,
+		who: &AccountIdOf<T>,
+		role: impl FnOnce(&TokenTeam<AccountIdOf<T>>) -> &AccountIdOf<T>,
+	) -> DispatchResult {
+		ensure!(<Tokens<T>>::contains_key(token), <Error<T>>::TokenNotFound);
+		let team = Self::team(token).ok_or(<Error<T>>::NoPermission)?;
+		ensure!(role(&team) == who, <Error<T>>::NoPermission);
+		Ok(())
+	}
 
-		Self::deposit_event(RawEvent::Mint(token, minter, value));
+	pub fn ensure_owner(token: u32, who: &AccountIdOf<T>) -> DispatchResult {
+		ensure!(<Tokens<T>>::contains_key(token), <Error<T>>::TokenNotFound);
+		ensure!(Self::owner(token).as_ref() == Some(who), <Error<T>>::NotTokenOwner);
+		Ok(())
 	}
 
-	pub fn burn_(burner: AccountIdOf<T>, token: u32, value: BalanceOf<T>) -> () {
-		let burner_balance = Self::balance((token, &burner));
-		let token_supply = Self::supply(token);
+	/// Move `value` from `from` to `to`. A remainder below the token's minimum balance is swept
+	/// to `to` as well, unless `keep_alive` is set, in which case the transfer fails.
+	pub fn transfer_(
+		token: u32,
+		from: AccountIdOf<T>,
+		to: AccountIdOf<T>,
+		value: BalanceOf<T>,
+		keep_alive: bool,
+	) -> DispatchResult {
+		Self::ensure_live(token)?;
+		ensure!(!Self::paused(token), <Error<T>>::TokenPaused);
+		ensure!(!Self::frozen(token, &from), <Error<T>>::AccountFrozen);
+		ensure!(T::AllowFrozenReceive::get() || !Self::frozen(token, &to), <Error<T>>::AccountFrozen);
+
+		let mut from_balance = Self::balance(token, &from)
+			.checked_sub(&value)
+			.ok_or(<Error<T>>::InsufficientBalance)?;
+
+		let mut amount = value;
+		if from != to {
+			let min_balance = Self::min_balance(token);
+			if !from_balance.is_zero() && from_balance < min_balance {
+				ensure!(!keep_alive, <Error<T>>::WouldLeaveDust);
+				amount = amount.saturating_add(from_balance);
+				from_balance = Zero::zero();
+			}
+			let to_balance = Self::balance(token, &to)
+				.checked_add(&amount)
+				.ok_or(<Error<T>>::Overflow)?;
+			ensure!(to_balance >= min_balance, <Error<T>>::BelowMinBalance);
+			Self::ensure_can_hold(token, &to)?;
 
-		<Balance<T>>::insert((token, &burner), burner_balance - value);
-		<Supply<T>>::insert(token, token_supply - value);
+			Self::set_balance(token, &from, from_balance);
+			Self::set_balance(token, &to, to_balance);
+		}
 
-		Self::deposit_event(RawEvent::Burn(token, burner, value));
+		Self::deposit_event(RawEvent::Transfer(token, from, to, amount));
+		Ok(())
+	}
+
+	/// Make each of `transfers` from `from` in a storage transaction, so that they are all
+	/// discarded if one fails.
+	// `#[transactional]` does not support `?` in the function body.
+	#[transactional]
+	fn transfer_all(
+		from: AccountIdOf<T>,
+		transfers: Vec<(TokenIndex, AccountIdOf<T>, BalanceOf<T>)>,
+	) -> DispatchResult {
+		transfers.into_iter()
+			.try_for_each(|(token, to, value)| Self::transfer_(token, from.clone(), to, value, false))
+	}
+
+	/// Credit `beneficiary` with `value` new tokens on behalf of `issuer`.
+	pub fn mint_(token: u32, beneficiary: AccountIdOf<T>, issuer: AccountIdOf<T>, value: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_live(token)?;
+		ensure!(T::AllowMintBurnWhenPaused::get() || !Self::paused(token), <Error<T>>::TokenPaused);
+		ensure!(T::AllowFrozenReceive::get() || !Self::frozen(token, &beneficiary), <Error<T>>::AccountFrozen);
+
+		let token_supply = Self::supply(token)
+			.checked_add(&value)
+			.ok_or(<Error<T>>::Overflow)?;
+		let max_supply = Self::tokens(token).and_then(|info| info.max_supply);
+		ensure!(max_supply.is_none_or(|max| token_supply <= max), <Error<T>>::MaxSupplyExceeded);
+		let beneficiary_balance = Self::balance(token, &beneficiary)
+			.checked_add(&value)
+			.ok_or(<Error<T>>::Overflow)?;
+		ensure!(beneficiary_balance >= Self::min_balance(token), <Error<T>>::BelowMinBalance);
+		Self::ensure_can_hold(token, &beneficiary)?;
+
+		Self::set_balance(token, &beneficiary, beneficiary_balance);
+		<Supply<T>>::insert(token, token_supply);
+
+		Self::deposit_event(RawEvent::Mint(token, beneficiary, issuer, value));
+		Ok(())
+	}
+
+	/// Destroy `value` tokens held by `who` on behalf of `admin`. A remainder below the token's
+	/// minimum balance is destroyed with them, unless `keep_alive` is set, in which case the burn
+	/// fails.
+	pub fn burn_(
+		token: u32,
+		who: AccountIdOf<T>,
+		admin: AccountIdOf<T>,
+		value: BalanceOf<T>,
+		keep_alive: bool,
+	) -> DispatchResult {
+		Self::ensure_live(token)?;
+		ensure!(T::AllowMintBurnWhenPaused::get() || !Self::paused(token), <Error<T>>::TokenPaused);
+
+		let mut who_balance = Self::balance(token, &who)
+			.checked_sub(&value)
+			.ok_or(<Error<T>>::InsufficientBalance)?;
+		let mut amount = value;
+		if !who_balance.is_zero() && who_balance < Self::min_balance(token) {
+			ensure!(!keep_alive, <Error<T>>::WouldLeaveDust);
+			amount = amount.saturating_add(who_balance);
+			who_balance = Zero::zero();
+		}
+		let token_supply = Self::supply(token)
+			.checked_sub(&amount)
+			.ok_or(<Error<T>>::Overflow)?;
+
+		Self::set_balance(token, &who, who_balance);
+		<Supply<T>>::insert(token, token_supply);
+
+		Self::deposit_event(RawEvent::Burn(token, who, admin, amount));
+		Ok(())
 	}	
 
+	/// The minimum balance of a token, zero if it does not exist.
+	pub fn min_balance(token: u32) -> BalanceOf<T> {
+		Self::tokens(token).map_or_else(Zero::zero, |info| info.min_balance)
+	}
+
+	/// Ensure `who` may hold the token: either it already does, the token is sufficient, or the
+	/// account exists in `frame_system`.
+	fn ensure_can_hold(token: u32, who: &AccountIdOf<T>) -> Result<(), Error<T>> {
+		ensure!(
+			<Balance<T>>::contains_key(token, who)
+				|| Self::is_sufficient(token)
+				|| <system::Account<T>>::contains_key(who),
+			<Error<T>>::NoAccount
+		);
+		Ok(())
+	}
+
+	/// Write a balance, removing the entry instead when it is zero. Each holder keeps a
+	/// `frame_system` reference on its account so that it is not reaped while holding tokens.
+	/// An account that only a sufficient token brought into existence is removed again with its
+	/// last reference.
+	fn set_balance(token: u32, who: &AccountIdOf<T>, balance: BalanceOf<T>) {
+		let existed = <Balance<T>>::contains_key(token, who);
+		if balance.is_zero() {
+			if existed {
+				<Balance<T>>::remove(token, who);
+				<system::Module<T>>::dec_ref(who);
+				let account = <system::Account<T>>::get(who);
+				if account.refcount == 0 && account.data == Default::default() {
+					<system::Module<T> as StoredMap<_, _>>::remove(who);
+				}
+			}
+		} else {
+			if !existed {
+				<system::Module<T>>::inc_ref(who);
+			}
+			<Balance<T>>::insert(token, who, balance);
+		}
+	}
+
+	pub fn approve_(token: u32, owner: AccountIdOf<T>, spender: AccountIdOf<T>, value: BalanceOf<T>) -> DispatchResult {
+		Self::ensure_live(token)?;
+		ensure!(!Self::paused(token), <Error<T>>::TokenPaused);
+
+		<Approval<T>>::insert(token, (&owner, &spender), value);
+
+		Self::deposit_event(RawEvent::Approval(token, owner, spender, value));
+		Ok(())
+	}
+
+	pub fn set_paused(token: u32, status: bool) -> DispatchResult {
+		ensure!(<Tokens<T>>::contains_key(token), <Error<T>>::TokenNotFound);
+
+		<Paused>::insert(token, status);
+
+		Self::deposit_event(RawEvent::PausedOperation(token, status));
+		Ok(())
+	}
+
 	pub fn get_balance(token: u32, who: AccountIdOf<T> ) -> BalanceOf<T> {
-		Self::balance((token, who))
+		Self::balance(token, who)
 	}		
 
+	/// All tokens held by `who`, with the balance of each.
+	pub fn tokens_of(who: &AccountIdOf<T>) -> Vec<(TokenIndex, BalanceOf<T>)> {
+		<Tokens<T>>::iter()
+			.filter(|(token, _)| <Balance<T>>::contains_key(token, who))
+			.map(|(token, _)| (token, Self::balance(token, who)))
+			.collect()
+	}
+
+	/// All accounts holding a balance of `token`.
+	pub fn holders(token: u32) -> Vec<(AccountIdOf<T>, BalanceOf<T>)> {
+		<Balance<T>>::iter_prefix(token).collect()
+	}
+
+	/// The root of the merkle tree containing `leaf`, given the sibling hashes on its path. Each
+	/// pair is hashed in sorted order, so the proof needs no left or right markers.
+	pub fn merkle_root(leaf: T::Hash, proof: &[T::Hash]) -> T::Hash {
+		proof.iter().fold(leaf, |node, sibling| {
+			let (first, second) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+			T::Hashing::hash(&[first.as_ref(), second.as_ref()].concat())
+		})
+	}
+
+	/// Check that `who` may claim `amount` from `airdrop` with `proof`, returning the airdrop and
+	/// the claimant's balance after the claim. This is every check `claim` makes, so that
+	/// `validate_unsigned` keeps out of the pool exactly the claims that would fail.
+	fn check_claim(
+		airdrop: u32,
+		who: &AccountIdOf<T>,
+		amount: BalanceOf<T>,
+		proof: &[T::Hash],
+	) -> Result<(AirdropInfoOf<T>, BalanceOf<T>), Error<T>> {
+		ensure!(proof.len() <= T::MaxProofLength::get() as usize, <Error<T>>::ProofTooLong);
+		let info = Self::airdrops(airdrop).ok_or(<Error<T>>::AirdropNotFound)?;
+		ensure!(<system::Module<T>>::block_number() <= info.expiry, <Error<T>>::AirdropExpired);
+		ensure!(!Self::claimed(airdrop, who), <Error<T>>::AlreadyClaimed);
+		let leaf = T::Hashing::hash_of(&(who, amount));
+		ensure!(Self::merkle_root(leaf, proof) == info.merkle_root, <Error<T>>::InvalidProof);
+		ensure!(amount <= info.remaining, <Error<T>>::AirdropExhausted);
+
+		let token = info.token;
+		Self::ensure_live(token)?;
+		ensure!(!Self::paused(token), <Error<T>>::TokenPaused);
+		ensure!(T::AllowFrozenReceive::get() || !Self::frozen(token, who), <Error<T>>::AccountFrozen);
+		let balance = Self::balance(token, who)
+			.checked_add(&amount)
+			.ok_or(<Error<T>>::Overflow)?;
+		ensure!(balance >= Self::min_balance(token), <Error<T>>::BelowMinBalance);
+		Self::ensure_can_hold(token, who)?;
+		Ok((info, balance))
+	}
+}
+
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
 
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		if let Call::claim(airdrop, who, amount, proof) = call {
+			let (info, _) = Self::check_claim(*airdrop, who, *amount, proof)
+				.map_err(|e| InvalidTransaction::Custom(e.as_u8()))?;
+			let now = <system::Module<T>>::block_number();
+
+			ValidTransaction::with_tag_prefix("TokenAirdrop")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((airdrop, who))
+				.longevity(info.expiry.saturating_sub(now).saturated_into::<u64>())
+				.propagate(true)
+				.build()
+		} else {
+			InvalidTransaction::Call.into()
+		}
+	}
 }
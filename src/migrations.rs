@@ -0,0 +1,253 @@
+//! Storage migrations, run from `on_runtime_upgrade` in order of `Releases`.
+
+use super::*;
+use frame_support::storage::migration::{put_storage_value, StorageIterator};
+use frame_support::StorageDoubleMap;
+
+/// Layout of `TokenInfo` up to `Releases::V1`, which duplicated the token owner.
+#[derive(Decode)]
+struct TokenInfoV1<AccountId, BlockNumber> {
+	name: Vec<u8>,
+	symbol: Vec<u8>,
+	#[allow(dead_code)]
+	owner: AccountId,
+	created: BlockNumber,
+}
+
+/// Layout of `TokenInfo` in `Releases::V2`, before decimals and the descriptive fields.
+#[derive(Encode, Decode)]
+struct TokenInfoV2<BlockNumber> {
+	name: Vec<u8>,
+	symbol: Vec<u8>,
+	created: BlockNumber,
+}
+
+/// Layout of `TokenInfo` from `Releases::V3` to `Releases::V6`, before the supply cap.
+#[derive(Encode, Decode)]
+struct TokenInfoV3<BlockNumber> {
+	name: Vec<u8>,
+	symbol: Vec<u8>,
+	decimals: u8,
+	icon_uri: Option<Vec<u8>>,
+	description: Vec<u8>,
+	website: Vec<u8>,
+	created: BlockNumber,
+}
+
+/// Layout of `TokenInfo` in `Releases::V7`, before the minimum balance.
+#[derive(Encode, Decode)]
+struct TokenInfoV7<Balance, BlockNumber> {
+	name: Vec<u8>,
+	symbol: Vec<u8>,
+	decimals: u8,
+	max_supply: Option<Balance>,
+	icon_uri: Option<Vec<u8>>,
+	description: Vec<u8>,
+	website: Vec<u8>,
+	created: BlockNumber,
+}
+
+pub fn migrate<T: Trait>() -> Weight {
+	let mut weight: Weight = T::DbWeight::get().reads(1);
+
+	if StorageVersion::get() == Releases::V1 {
+		weight = weight.saturating_add(migrate_to_v2::<T>());
+	}
+	if StorageVersion::get() == Releases::V2 {
+		weight = weight.saturating_add(migrate_to_v3::<T>());
+	}
+	if StorageVersion::get() == Releases::V3 {
+		weight = weight.saturating_add(migrate_to_v4::<T>());
+	}
+	if StorageVersion::get() == Releases::V4 {
+		weight = weight.saturating_add(migrate_to_v5::<T>());
+	}
+	if StorageVersion::get() == Releases::V5 {
+		weight = weight.saturating_add(migrate_to_v6::<T>());
+	}
+	if StorageVersion::get() == Releases::V6 {
+		weight = weight.saturating_add(migrate_to_v7::<T>());
+	}
+	if StorageVersion::get() == Releases::V7 {
+		weight = weight.saturating_add(migrate_to_v8::<T>());
+	}
+	if StorageVersion::get() == Releases::V8 {
+		weight = weight.saturating_add(migrate_to_v9::<T>());
+	}
+
+	weight
+}
+
+/// Rewrite every `Tokens` entry from one historical layout to the next, returning the weight.
+///
+/// The typed `translate` of the map only knows the current layout, so intermediate steps go
+/// through the raw storage keys instead.
+fn translate_tokens<T: Trait, O: Decode, N: Encode>(f: impl Fn(O) -> N) -> Weight {
+	let entries = StorageIterator::<O>::new(b"TokenStore", b"Tokens").collect::<Vec<_>>();
+	let count = entries.len() as Weight;
+	for (key, old) in entries {
+		put_storage_value(b"TokenStore", b"Tokens", &key, f(old));
+	}
+	T::DbWeight::get().reads_writes(count, count)
+}
+
+/// Drop the `owner` field from `TokenInfo`. The `Owner` map, which the admin checks have always
+/// used, is kept as the only record of ownership.
+fn migrate_to_v2<T: Trait>() -> Weight {
+	let weight = translate_tokens::<T, TokenInfoV1<T::AccountId, T::BlockNumber>, _>(|old| {
+		TokenInfoV2 {
+			name: old.name,
+			symbol: old.symbol,
+			created: old.created,
+		}
+	});
+	StorageVersion::put(Releases::V2);
+
+	weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Add `decimals`, `icon_uri`, `description` and `website` to `TokenInfo`. Existing tokens get
+/// zero decimals and empty metadata until their owner calls `set_metadata`.
+fn migrate_to_v3<T: Trait>() -> Weight {
+	let weight = translate_tokens::<T, TokenInfoV2<T::BlockNumber>, _>(|old| {
+		TokenInfoV3 {
+			name: old.name,
+			symbol: old.symbol,
+			decimals: 0,
+			icon_uri: None,
+			description: Vec::new(),
+			website: Vec::new(),
+			created: old.created,
+		}
+	});
+	StorageVersion::put(Releases::V3);
+
+	weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Fill `SymbolToToken` from the existing tokens. Where several tokens share a symbol the oldest
+/// one keeps it; the others can still be looked up by index.
+fn migrate_to_v4<T: Trait>() -> Weight {
+	let mut tokens = StorageIterator::<TokenInfoV3<T::BlockNumber>>::new(b"TokenStore", b"Tokens")
+		.filter_map(|(key, info)| decode_key::<TokenIndex>(&key).map(|index| (index, info)))
+		.collect::<Vec<_>>();
+	tokens.sort_by_key(|(index, _)| *index);
+
+	let mut writes: Weight = 1;
+	for (index, info) in &tokens {
+		if !<SymbolToToken>::contains_key(&info.symbol) {
+			<SymbolToToken>::insert(&info.symbol, index);
+			writes += 1;
+		}
+	}
+	StorageVersion::put(Releases::V4);
+
+	let count = tokens.len() as Weight;
+	T::DbWeight::get().reads_writes(count.saturating_mul(2).saturating_add(1), writes)
+}
+
+/// Decode the key of a `blake2_128_concat` map entry from the raw key suffix.
+fn decode_key<K: Decode>(suffix: &[u8]) -> Option<K> {
+	suffix.get(16..).and_then(|mut key| K::decode(&mut key).ok())
+}
+
+/// Re-key `Balance` from `(token, account)` and `Approval` from `(token, owner, spender)` into
+/// double maps with the token as the first key.
+fn migrate_to_v5<T: Trait>() -> Weight {
+	let balances = StorageIterator::<BalanceOf<T>>::new(b"TokenStore", b"Balance")
+		.drain()
+		.collect::<Vec<_>>();
+	let approvals = StorageIterator::<BalanceOf<T>>::new(b"TokenStore", b"Approval")
+		.drain()
+		.collect::<Vec<_>>();
+	let count = balances.len().saturating_add(approvals.len()) as Weight;
+
+	for (key, balance) in balances {
+		if let Some((token, who)) = decode_key::<(TokenIndex, T::AccountId)>(&key) {
+			<Balance<T>>::insert(token, who, balance);
+		}
+	}
+	for (key, allowance) in approvals {
+		if let Some((token, owner, spender)) = decode_key::<(TokenIndex, T::AccountId, T::AccountId)>(&key) {
+			<Approval<T>>::insert(token, (owner, spender), allowance);
+		}
+	}
+	StorageVersion::put(Releases::V5);
+
+	T::DbWeight::get().reads_writes(count, count.saturating_mul(2).saturating_add(1))
+}
+
+/// Give the owner of each token every role, so that permissions are unchanged by the upgrade.
+fn migrate_to_v6<T: Trait>() -> Weight {
+	let mut count: Weight = 0;
+	for (token, owner) in <Owner<T>>::iter() {
+		<Team<T>>::insert(token, TokenTeam::all(&owner));
+		count += 1;
+	}
+	StorageVersion::put(Releases::V6);
+
+	T::DbWeight::get().reads_writes(count, count.saturating_add(1))
+}
+
+/// Add `max_supply` to `TokenInfo`. Existing tokens stay uncapped.
+fn migrate_to_v7<T: Trait>() -> Weight {
+	let weight = translate_tokens::<T, TokenInfoV3<T::BlockNumber>, _>(|old| {
+		TokenInfoV7::<BalanceOf<T>, _> {
+			name: old.name,
+			symbol: old.symbol,
+			decimals: old.decimals,
+			max_supply: None,
+			icon_uri: old.icon_uri,
+			description: old.description,
+			website: old.website,
+			created: old.created,
+		}
+	});
+	StorageVersion::put(Releases::V7);
+
+	weight.saturating_add(T::DbWeight::get().writes(1))
+}
+
+/// Add `min_balance` to `TokenInfo`, zero for existing tokens, and drop zero entries of
+/// `Balance`, which are no longer kept.
+fn migrate_to_v8<T: Trait>() -> Weight {
+	let weight = translate_tokens::<T, TokenInfoV7<BalanceOf<T>, T::BlockNumber>, _>(|old| {
+		TokenInfoOf::<T> {
+			name: old.name,
+			symbol: old.symbol,
+			decimals: old.decimals,
+			max_supply: old.max_supply,
+			min_balance: Zero::zero(),
+			icon_uri: old.icon_uri,
+			description: old.description,
+			website: old.website,
+			created: old.created,
+		}
+	});
+
+	let mut reads: Weight = 0;
+	let empty = <Balance<T>>::iter()
+		.inspect(|_| reads += 1)
+		.filter(|(_, _, balance)| balance.is_zero())
+		.map(|(token, who, _)| (token, who))
+		.collect::<Vec<_>>();
+	for (token, who) in &empty {
+		<Balance<T>>::remove(token, who);
+	}
+	StorageVersion::put(Releases::V8);
+
+	weight.saturating_add(T::DbWeight::get().reads_writes(reads, (empty.len() as Weight).saturating_add(1)))
+}
+
+/// Take a `frame_system` reference on the account of every existing holder, as `set_balance`
+/// now does for new ones.
+fn migrate_to_v9<T: Trait>() -> Weight {
+	let mut count: Weight = 0;
+	for (_, who, _) in <Balance<T>>::iter() {
+		<system::Module<T>>::inc_ref(&who);
+		count += 1;
+	}
+	StorageVersion::put(Releases::V9);
+
+	T::DbWeight::get().reads_writes(count.saturating_mul(2), count.saturating_add(1))
+}
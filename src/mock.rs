@@ -0,0 +1,163 @@
+use crate::{Module, Trait};
+use sp_core::H256;
+use frame_support::{impl_outer_origin, impl_outer_event, parameter_types, traits::Get, weights::Weight};
+use std::cell::RefCell;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+};
+use frame_system::{self as system, EnsureRoot, EnsureSigned};
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+mod token {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		balances<T>,
+		token<T>,
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type PalletInfo = ();
+	type AccountData = balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl balances::Trait for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = TestEvent;
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type MaxLocks = MaxLocks;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const AllowFrozenReceive: bool = false;
+	pub const TokenDeposit: u64 = 10;
+	pub const MetadataDepositPerByte: u64 = 1;
+	pub const MaxNameLength: u32 = 16;
+	pub const MaxSymbolLength: u32 = 6;
+	pub const MaxIconUriLength: u32 = 16;
+	pub const MaxDescriptionLength: u32 = 64;
+	pub const MaxWebsiteLength: u32 = 16;
+	pub const MaxBatchSize: u32 = 3;
+	pub const UnsignedPriority: u64 = 100;
+	pub const MaxProofLength: u32 = 32;
+}
+
+thread_local! {
+	static ALLOW_MINT_BURN_WHEN_PAUSED: RefCell<bool> = const { RefCell::new(false) };
+}
+
+// Off by default; tests switch it on with `set_allow_mint_burn_when_paused`.
+pub struct AllowMintBurnWhenPaused;
+impl Get<bool> for AllowMintBurnWhenPaused {
+	fn get() -> bool {
+		ALLOW_MINT_BURN_WHEN_PAUSED.with(|v| *v.borrow())
+	}
+}
+
+pub fn set_allow_mint_burn_when_paused(allow: bool) {
+	ALLOW_MINT_BURN_WHEN_PAUSED.with(|v| *v.borrow_mut() = allow);
+}
+
+impl Trait for Test {
+	type Event = TestEvent;
+	type Currency = Balances;
+	type AllowMintBurnWhenPaused = AllowMintBurnWhenPaused;
+	type AllowFrozenReceive = AllowFrozenReceive;
+	type CreateOrigin = EnsureSigned<u64>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type TokenDeposit = TokenDeposit;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type MaxNameLength = MaxNameLength;
+	type MaxSymbolLength = MaxSymbolLength;
+	type MaxIconUriLength = MaxIconUriLength;
+	type MaxDescriptionLength = MaxDescriptionLength;
+	type MaxWebsiteLength = MaxWebsiteLength;
+	type MaxBatchSize = MaxBatchSize;
+	type UnsignedPriority = UnsignedPriority;
+	type MaxProofLength = MaxProofLength;
+	type WeightInfo = ();
+}
+
+pub type System = system::Module<Test>;
+pub type Balances = balances::Module<Test>;
+pub type TokenModule = Module<Test>;
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with_tokens(vec![], vec![])
+}
+
+// `(id, name, symbol, decimals, owner)`, as in `GenesisConfig::tokens`.
+pub type GenesisToken = (u32, Vec<u8>, Vec<u8>, u8, u64);
+
+// Build genesis storage with the given tokens, see `GenesisConfig` of the pallet.
+pub fn new_test_ext_with_tokens(
+	tokens: Vec<GenesisToken>,
+	token_balances: Vec<(u32, u64, u64)>,
+) -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100)],
+	}.assimilate_storage(&mut t).unwrap();
+	crate::GenesisConfig::<Test> {
+		tokens,
+		balances: token_balances,
+	}.assimilate_storage(&mut t).unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn last_event() -> TestEvent {
+	System::events().pop().expect("an event was deposited").event
+}
@@ -1,23 +1,1129 @@
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{Balance, Error, RawEvent, Releases, StorageVersion, TokenTeam, Tokens, mock::*};
+use frame_support::{
+	assert_ok, assert_noop, Blake2_128Concat, StorageDoubleMap, StorageHasher, StorageMap, StorageValue,
+	storage::migration::put_storage_value, traits::OnRuntimeUpgrade,
+};
+use frame_support::unsigned::ValidateUnsigned;
+use parity_scale_codec::Encode;
+use sp_core::H256;
+use sp_runtime::{
+	DispatchError,
+	traits::{BlakeTwo256, Hash},
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+};
+
+fn token_event(event: RawEvent<u64, u64>) -> TestEvent {
+	TestEvent::token(event)
+}
+
+// Creates token 0, owned by account 1 with 1000 units. Reserves 10 + 12 bytes of metadata.
+fn create_token() {
+	assert_ok!(TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, None, 0));
+}
+
+#[test]
+fn create_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+
+		assert_eq!(TokenModule::token_count(), 1);
+		assert!(TokenModule::tokens(0).is_some());
+		assert_eq!(TokenModule::balance(0, 1), 1000);
+		assert_eq!(TokenModule::supply(0), 1000);
+		assert_eq!(TokenModule::owner(0), Some(1));
+		assert_eq!(TokenModule::token_by_symbol(b"DCB".to_vec()), Some(0));
+		assert_eq!(TokenModule::deposit(0), Some((1, 22)));
+		assert_eq!(Balances::reserved_balance(1), 22);
+		assert_eq!(last_event(), token_event(RawEvent::Created(0, 1)));
+	});
+}
+
+#[test]
+fn create_for_another_owner_credits_the_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenModule::create(Origin::signed(1), 2, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, None, 0));
+
+		assert_eq!(TokenModule::owner(0), Some(2));
+		assert_eq!(TokenModule::balance(0, 2), 1000);
+		assert_eq!(TokenModule::balance(0, 1), 0);
+		assert_eq!(Balances::reserved_balance(1), 22);
+	});
+}
+
+#[test]
+fn create_fails_with_invalid_metadata() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"dcb".to_vec(), 12, 1000, None, 0),
+			Error::<Test>::InvalidSymbol
+		);
+		assert_noop!(
+			TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), Vec::new(), 12, 1000, None, 0),
+			Error::<Test>::InvalidSymbol
+		);
+		assert_noop!(
+			TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"DCBTOKEN".to_vec(), 12, 1000, None, 0),
+			Error::<Test>::SymbolTooLong
+		);
+		assert_noop!(
+			TokenModule::create(Origin::signed(1), 1, vec![0xff, 0xfe], b"DCB".to_vec(), 12, 1000, None, 0),
+			Error::<Test>::InvalidName
+		);
+		assert_noop!(
+			TokenModule::create(Origin::signed(1), 1, vec![b'a'; 17], b"DCB".to_vec(), 12, 1000, None, 0),
+			Error::<Test>::NameTooLong
+		);
+	});
+}
+
+#[test]
+fn create_fails_without_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TokenModule::create(Origin::signed(4), 4, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, None, 0),
+			balances::Error::<Test, _>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn create_requires_unique_unreserved_symbol() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(
+			TokenModule::create(Origin::signed(2), 2, b"Fake DCB".to_vec(), b"DCB".to_vec(), 12, 1000, None, 0),
+			Error::<Test>::SymbolTaken
+		);
+
+		assert_ok!(TokenModule::reserve_symbol(Origin::root(), b"USDD".to_vec()));
+		assert_eq!(last_event(), token_event(RawEvent::SymbolReserved(b"USDD".to_vec())));
+		assert_noop!(
+			TokenModule::create(Origin::signed(2), 2, b"Dollar".to_vec(), b"USDD".to_vec(), 6, 1000, None, 0),
+			Error::<Test>::SymbolReserved
+		);
+
+		assert_ok!(TokenModule::release_symbol(Origin::root(), b"USDD".to_vec()));
+		assert_ok!(TokenModule::create(Origin::signed(2), 2, b"Dollar".to_vec(), b"USDD".to_vec(), 6, 1000, None, 0));
+		assert_eq!(TokenModule::token_by_symbol(b"USDD".to_vec()), Some(1));
+	});
+}
+
+#[test]
+fn force_create_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TokenModule::force_create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, None, 0),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(TokenModule::reserve_symbol(Origin::root(), b"DCB".to_vec()));
+		assert_ok!(TokenModule::force_create(Origin::root(), 4, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, None, 0));
+
+		assert_eq!(TokenModule::owner(0), Some(4));
+		assert_eq!(TokenModule::balance(0, 4), 1000);
+		assert_eq!(TokenModule::deposit(0), None);
+		assert!(!TokenModule::symbol_reserved(b"DCB".to_vec()));
+		assert_eq!(TokenModule::token_by_symbol(b"DCB".to_vec()), Some(0));
+	});
+}
+
+#[test]
+fn transfer_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 300));
+
+		assert_eq!(TokenModule::balance(0, 1), 700);
+		assert_eq!(TokenModule::balance(0, 2), 300);
+		assert_eq!(last_event(), token_event(RawEvent::Transfer(0, 1, 2, 300)));
+	});
+}
+
+#[test]
+fn transfer_fails_with_insufficient_balance() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(TokenModule::transfer(Origin::signed(1), 0, 2, 1001), Error::<Test>::InsufficientBalance);
+		assert_noop!(TokenModule::transfer(Origin::signed(2), 0, 1, 1), Error::<Test>::InsufficientBalance);
+	});
+}
+
+#[test]
+fn transfer_fails_for_unknown_token() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TokenModule::transfer(Origin::signed(1), 0, 2, 0), Error::<Test>::TokenNotFound);
+	});
+}
+
+#[test]
+fn approvals_work() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::approve(Origin::signed(1), 0, 2, 100));
+		assert_eq!(last_event(), token_event(RawEvent::Approval(0, 1, 2, 100)));
+		assert_eq!(TokenModule::approval(0, (1, 2)), 100);
+
+		assert_ok!(TokenModule::increase_allowance(Origin::signed(1), 0, 2, 50));
+		assert_eq!(TokenModule::approval(0, (1, 2)), 150);
+
+		assert_ok!(TokenModule::decrease_allowance(Origin::signed(1), 0, 2, 120));
+		assert_eq!(TokenModule::approval(0, (1, 2)), 30);
+		assert_eq!(last_event(), token_event(RawEvent::Approval(0, 1, 2, 30)));
+
+		assert_noop!(
+			TokenModule::decrease_allowance(Origin::signed(1), 0, 2, 31),
+			Error::<Test>::InsufficientApproval
+		);
+		assert_noop!(
+			TokenModule::increase_allowance(Origin::signed(1), 0, 2, u64::MAX),
+			Error::<Test>::Overflow
+		);
+	});
+}
+
+#[test]
+fn transfer_from_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::approve(Origin::signed(1), 0, 2, 100));
+		assert_ok!(TokenModule::transfer_from(Origin::signed(2), 0, 1, 3, 60));
+
+		assert_eq!(TokenModule::balance(0, 1), 940);
+		assert_eq!(TokenModule::balance(0, 2), 0);
+		assert_eq!(TokenModule::balance(0, 3), 60);
+		assert_eq!(TokenModule::approval(0, (1, 2)), 40);
+		assert_eq!(last_event(), token_event(RawEvent::TransferFrom(0, 1, 2, 60)));
+	});
+}
+
+#[test]
+fn transfers_remove_empty_balances() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 1000));
+
+		assert_eq!(TokenModule::holders(0), vec![(2, 1000)]);
+		assert!(!Balance::<Test>::contains_key(0, 1));
+	});
+}
+
+#[test]
+fn min_balance_sweeps_or_keeps_alive() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 5, None, 10),
+			Error::<Test>::BelowMinBalance
+		);
+		assert_ok!(TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, None, 10));
+
+		// Receivers must end up with at least the minimum balance.
+		assert_noop!(TokenModule::transfer(Origin::signed(1), 0, 2, 9), Error::<Test>::BelowMinBalance);
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 3, 9), Error::<Test>::BelowMinBalance);
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 100));
+
+		// Leaving 5 behind would create dust: keep-alive refuses, a plain transfer sweeps it.
+		assert_noop!(TokenModule::transfer_keep_alive(Origin::signed(2), 0, 3, 95), Error::<Test>::WouldLeaveDust);
+		assert_ok!(TokenModule::transfer_keep_alive(Origin::signed(2), 0, 3, 90));
+		assert_ok!(TokenModule::transfer(Origin::signed(3), 0, 2, 85));
+		assert_eq!(last_event(), token_event(RawEvent::Transfer(0, 3, 2, 90)));
+		assert_eq!(TokenModule::balance(0, 2), 100);
+		assert!(!Balance::<Test>::contains_key(0, 3));
+
+		// Burning down to dust burns the dust as well.
+		assert_ok!(TokenModule::burn_own(Origin::signed(2), 0, 95));
+		assert_eq!(last_event(), token_event(RawEvent::Burn(0, 2, 2, 100)));
+		assert!(!Balance::<Test>::contains_key(0, 2));
+		assert_eq!(TokenModule::supply(0), 900);
+	});
+}
+
+#[test]
+fn allowances_never_sweep_dust() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, None, 100));
+		assert_ok!(TokenModule::approve(Origin::signed(1), 0, 2, 950));
+
+		// Spending the whole allowance would leave 50 behind, which must not be swept or burned.
+		assert_noop!(TokenModule::transfer_from(Origin::signed(2), 0, 1, 3, 950), Error::<Test>::WouldLeaveDust);
+		assert_noop!(TokenModule::burn_with_allowance(Origin::signed(2), 0, 1, 950), Error::<Test>::WouldLeaveDust);
+
+		assert_ok!(TokenModule::transfer_from(Origin::signed(2), 0, 1, 3, 400));
+		assert_ok!(TokenModule::burn_with_allowance(Origin::signed(2), 0, 1, 500));
+		assert_eq!(last_event(), token_event(RawEvent::Burn(0, 1, 2, 500)));
+		assert_eq!(TokenModule::balance(0, 1), 100);
+		assert_eq!(TokenModule::balance(0, 3), 400);
+		assert_eq!(TokenModule::approval(0, (1, 2)), 50);
+		assert_eq!(TokenModule::supply(0), 500);
+	});
+}
+
+#[test]
+fn allowances_can_take_the_whole_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, None, 100));
+		assert_ok!(TokenModule::approve(Origin::signed(1), 0, 2, 1000));
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 3, 600));
+
+		// Emptying an account leaves no dust behind.
+		assert_ok!(TokenModule::transfer_from(Origin::signed(2), 0, 1, 3, 400));
+		assert!(!Balance::<Test>::contains_key(0, 1));
+		assert_eq!(TokenModule::balance(0, 3), 1000);
+
+		assert_ok!(TokenModule::approve(Origin::signed(3), 0, 2, 1000));
+		assert_ok!(TokenModule::burn_with_allowance(Origin::signed(2), 0, 3, 1000));
+		assert_eq!(last_event(), token_event(RawEvent::Burn(0, 3, 2, 1000)));
+		assert!(!Balance::<Test>::contains_key(0, 3));
+		assert_eq!(TokenModule::approval(0, (3, 2)), 0);
+		assert_eq!(TokenModule::supply(0), 0);
+	});
+}
+
+#[test]
+fn holders_keep_a_system_reference() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_eq!(System::refs(&1), 1);
+
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 100));
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 100));
+		assert_eq!(System::refs(&2), 1);
+
+		assert_ok!(TokenModule::transfer(Origin::signed(2), 0, 3, 200));
+		assert_eq!(System::refs(&2), 0);
+		assert_eq!(System::refs(&3), 1);
+
+		assert_ok!(TokenModule::start_destroy(Origin::root(), 0));
+		assert_ok!(TokenModule::destroy_accounts(Origin::signed(3), 0, 10));
+		assert_eq!(System::refs(&1), 0);
+		assert_eq!(System::refs(&3), 0);
+	});
+}
+
+#[test]
+fn only_sufficient_tokens_create_accounts() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		// Account 4 has no native balance.
+		assert_noop!(TokenModule::transfer(Origin::signed(1), 0, 4, 100), Error::<Test>::NoAccount);
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 4, 100), Error::<Test>::NoAccount);
+
+		assert_noop!(TokenModule::set_sufficient(Origin::signed(1), 0, true), DispatchError::BadOrigin);
+		assert_noop!(TokenModule::set_sufficient(Origin::root(), 1, true), Error::<Test>::TokenNotFound);
+		assert_ok!(TokenModule::set_sufficient(Origin::root(), 0, true));
+		assert_eq!(last_event(), token_event(RawEvent::SufficiencyChanged(0, true)));
+
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 4, 100));
+		assert_eq!(System::refs(&4), 1);
+
+		// Existing holders keep receiving after the token stops being sufficient.
+		assert_ok!(TokenModule::set_sufficient(Origin::root(), 0, false));
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 4, 100));
+		assert_noop!(TokenModule::transfer(Origin::signed(1), 0, 5, 100), Error::<Test>::NoAccount);
+	});
+}
+
+#[test]
+fn emptied_sufficient_holders_lose_their_account() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::create(Origin::signed(2), 2, b"Dollar".to_vec(), b"USDD".to_vec(), 6, 500, None, 0));
+		assert_ok!(TokenModule::set_sufficient(Origin::root(), 0, true));
+
+		// Account 4 has no native balance; the sufficient token alone keeps it alive.
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 4, 100));
+		assert!(frame_system::Account::<Test>::contains_key(4));
+		assert_ok!(TokenModule::transfer(Origin::signed(4), 0, 1, 100));
+		assert!(!frame_system::Account::<Test>::contains_key(4));
+
+		assert_noop!(TokenModule::transfer(Origin::signed(2), 1, 4, 100), Error::<Test>::NoAccount);
+	});
+}
+
+#[test]
+fn transfer_batch_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::transfer_batch(Origin::signed(1), 0, vec![(2, 100), (3, 200)]));
+
+		assert_eq!(TokenModule::balance(0, 1), 700);
+		assert_eq!(TokenModule::balance(0, 2), 100);
+		assert_eq!(TokenModule::balance(0, 3), 200);
+		assert_eq!(last_event(), token_event(RawEvent::Transfer(0, 1, 3, 200)));
+	});
+}
+
+#[test]
+fn transfer_batch_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(
+			TokenModule::transfer_batch(Origin::signed(1), 0, vec![(2, 1), (2, 1), (2, 1), (2, 1)]),
+			Error::<Test>::BatchTooLarge
+		);
+		// The second transfer overdraws, so the first is rolled back too.
+		assert_noop!(
+			TokenModule::transfer_batch(Origin::signed(1), 0, vec![(2, 600), (3, 600)]),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(TokenModule::balance(0, 1), 1000);
+		assert_eq!(TokenModule::balance(0, 2), 0);
+	});
+}
+
+#[test]
+fn transfer_multi_moves_several_tokens() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::create(Origin::signed(1), 1, b"Fee".to_vec(), b"FEE".to_vec(), 0, 50, None, 0));
+
+		assert_ok!(TokenModule::transfer_multi(Origin::signed(1), vec![(0, 2, 300), (1, 3, 5)]));
+		assert_eq!(TokenModule::balance(0, 2), 300);
+		assert_eq!(TokenModule::balance(1, 3), 5);
+		assert_eq!(TokenModule::balance(1, 1), 45);
+
+		// The fee leg fails, so the payment leg is discarded as well.
+		assert_noop!(
+			TokenModule::transfer_multi(Origin::signed(1), vec![(0, 2, 300), (1, 3, 46)]),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			TokenModule::transfer_multi(Origin::signed(1), vec![(0, 2, 1), (1, 2, 1), (0, 3, 1), (1, 3, 1)]),
+			Error::<Test>::BatchTooLarge
+		);
+	});
+}
+
+#[test]
+fn transfer_from_requires_approval() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(
+			TokenModule::transfer_from(Origin::signed(2), 0, 1, 2, 1),
+			Error::<Test>::InsufficientApproval
+		);
+
+		assert_ok!(TokenModule::approve(Origin::signed(1), 0, 2, 100));
+		assert_noop!(
+			TokenModule::transfer_from(Origin::signed(2), 0, 1, 2, 101),
+			Error::<Test>::InsufficientApproval
+		);
+
+		// The allowance is left untouched when the transfer itself fails.
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 3, 950));
+		assert_noop!(
+			TokenModule::transfer_from(Origin::signed(2), 0, 1, 2, 100),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(TokenModule::approval(0, (1, 2)), 100);
+	});
+}
+
+#[test]
+fn mint_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::mint(Origin::signed(1), 0, 1, 500));
+
+		assert_eq!(TokenModule::balance(0, 1), 1500);
+		assert_eq!(TokenModule::supply(0), 1500);
+		assert_eq!(last_event(), token_event(RawEvent::Mint(0, 1, 1, 500)));
+	});
+}
+
+#[test]
+fn mint_fails_for_non_owner_or_overflow() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(TokenModule::mint(Origin::signed(2), 0, 2, 500), Error::<Test>::NoPermission);
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 1, u64::MAX), Error::<Test>::Overflow);
+		assert_noop!(TokenModule::mint(Origin::signed(1), 1, 1, 500), Error::<Test>::TokenNotFound);
+	});
+}
+
+#[test]
+fn mint_to_beneficiary_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::mint(Origin::signed(1), 0, 2, 300));
+
+		assert_eq!(TokenModule::balance(0, 1), 1000);
+		assert_eq!(TokenModule::balance(0, 2), 300);
+		assert_eq!(TokenModule::supply(0), 1300);
+		assert_eq!(last_event(), token_event(RawEvent::Mint(0, 2, 1, 300)));
+	});
+}
+
+#[test]
+fn mint_respects_max_supply() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, Some(999), 0),
+			Error::<Test>::MaxSupplyExceeded
+		);
+		assert_ok!(TokenModule::create(Origin::signed(1), 1, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, Some(1500), 0));
+		assert_eq!(TokenModule::tokens(0).unwrap().max_supply, Some(1500));
+
+		assert_ok!(TokenModule::mint(Origin::signed(1), 0, 2, 500));
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 2, 1), Error::<Test>::MaxSupplyExceeded);
+		// Burning makes room again.
+		assert_ok!(TokenModule::burn(Origin::signed(1), 0, 100));
+		assert_ok!(TokenModule::mint(Origin::signed(1), 0, 2, 100));
+	});
+}
+
+#[test]
+fn max_supply_can_only_be_lowered() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(TokenModule::set_max_supply(Origin::signed(2), 0, 2000), Error::<Test>::NotTokenOwner);
+		assert_noop!(TokenModule::set_max_supply(Origin::signed(1), 0, 999), Error::<Test>::InvalidMaxSupply);
+
+		assert_ok!(TokenModule::set_max_supply(Origin::signed(1), 0, 2000));
+		assert_eq!(last_event(), token_event(RawEvent::MaxSupplyLowered(0, 2000)));
+		assert_noop!(TokenModule::set_max_supply(Origin::signed(1), 0, 2001), Error::<Test>::InvalidMaxSupply);
+		assert_ok!(TokenModule::set_max_supply(Origin::signed(1), 0, 1000));
+		assert_eq!(TokenModule::tokens(0).unwrap().max_supply, Some(1000));
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 1, 1), Error::<Test>::MaxSupplyExceeded);
+	});
+}
+
+#[test]
+fn burn_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::burn(Origin::signed(1), 0, 400));
+
+		assert_eq!(TokenModule::balance(0, 1), 600);
+		assert_eq!(TokenModule::supply(0), 600);
+		assert_eq!(last_event(), token_event(RawEvent::Burn(0, 1, 1, 400)));
+	});
+}
+
+#[test]
+fn burn_from_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 300));
+		assert_noop!(TokenModule::burn_from(Origin::signed(2), 0, 2, 100), Error::<Test>::NoPermission);
+		assert_noop!(TokenModule::burn_from(Origin::signed(1), 0, 2, 301), Error::<Test>::InsufficientBalance);
+
+		assert_ok!(TokenModule::burn_from(Origin::signed(1), 0, 2, 100));
+		assert_eq!(TokenModule::balance(0, 1), 700);
+		assert_eq!(TokenModule::balance(0, 2), 200);
+		assert_eq!(TokenModule::supply(0), 900);
+		assert_eq!(last_event(), token_event(RawEvent::Burn(0, 2, 1, 100)));
+	});
+}
+
+#[test]
+fn holders_can_burn_their_own_tokens() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 300));
+		assert_noop!(TokenModule::burn_own(Origin::signed(2), 0, 301), Error::<Test>::InsufficientBalance);
+
+		assert_ok!(TokenModule::burn_own(Origin::signed(2), 0, 100));
+		assert_eq!(TokenModule::balance(0, 2), 200);
+		assert_eq!(TokenModule::supply(0), 900);
+		assert_eq!(last_event(), token_event(RawEvent::Burn(0, 2, 2, 100)));
+
+		assert_ok!(TokenModule::freeze(Origin::signed(1), 0, 2));
+		assert_noop!(TokenModule::burn_own(Origin::signed(2), 0, 100), Error::<Test>::AccountFrozen);
+	});
+}
+
+#[test]
+fn burn_with_allowance_spends_approval() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(TokenModule::burn_with_allowance(Origin::signed(3), 0, 1, 100), Error::<Test>::InsufficientApproval);
+		assert_ok!(TokenModule::approve(Origin::signed(1), 0, 3, 150));
+
+		assert_ok!(TokenModule::burn_with_allowance(Origin::signed(3), 0, 1, 100));
+		assert_eq!(TokenModule::balance(0, 1), 900);
+		assert_eq!(TokenModule::supply(0), 900);
+		assert_eq!(TokenModule::approval(0, (1, 3)), 50);
+		assert_eq!(last_event(), token_event(RawEvent::Burn(0, 1, 3, 100)));
+		assert_noop!(TokenModule::burn_with_allowance(Origin::signed(3), 0, 1, 51), Error::<Test>::InsufficientApproval);
+	});
+}
+
+#[test]
+fn burn_fails_for_non_owner_or_insufficient_balance() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(TokenModule::burn(Origin::signed(2), 0, 1), Error::<Test>::NoPermission);
+		assert_noop!(TokenModule::burn(Origin::signed(1), 0, 1001), Error::<Test>::InsufficientBalance);
+	});
+}
+
+#[test]
+fn pause_blocks_balance_movements() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::approve(Origin::signed(1), 0, 2, 100));
+
+		assert_noop!(TokenModule::pause(Origin::signed(2), 0, true), Error::<Test>::NoPermission);
+		assert_ok!(TokenModule::pause(Origin::signed(1), 0, true));
+		assert!(TokenModule::paused(0));
+		assert_eq!(last_event(), token_event(RawEvent::PausedOperation(0, true)));
+
+		assert_noop!(TokenModule::transfer(Origin::signed(1), 0, 2, 1), Error::<Test>::TokenPaused);
+		assert_noop!(TokenModule::transfer_from(Origin::signed(2), 0, 1, 2, 1), Error::<Test>::TokenPaused);
+		assert_noop!(TokenModule::approve(Origin::signed(1), 0, 2, 1), Error::<Test>::TokenPaused);
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 1, 1), Error::<Test>::TokenPaused);
+		assert_noop!(TokenModule::burn(Origin::signed(1), 0, 1), Error::<Test>::TokenPaused);
+		assert_noop!(TokenModule::burn_own(Origin::signed(1), 0, 1), Error::<Test>::TokenPaused);
+		assert_noop!(TokenModule::burn_with_allowance(Origin::signed(2), 0, 1, 1), Error::<Test>::TokenPaused);
+
+		assert_ok!(TokenModule::unpause(Origin::signed(1), 0));
+		assert!(!TokenModule::paused(0));
+		assert_eq!(last_event(), token_event(RawEvent::PausedOperation(0, false)));
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 1));
+	});
+}
+
+#[test]
+fn mint_burn_policy_only_covers_the_team() {
+	new_test_ext().execute_with(|| {
+		set_allow_mint_burn_when_paused(true);
+		create_token();
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 100));
+		assert_ok!(TokenModule::approve(Origin::signed(2), 0, 3, 50));
+		assert_ok!(TokenModule::pause(Origin::signed(1), 0, true));
+
+		assert_ok!(TokenModule::mint(Origin::signed(1), 0, 1, 10));
+		assert_ok!(TokenModule::burn(Origin::signed(1), 0, 10));
+		assert_ok!(TokenModule::burn_from(Origin::signed(1), 0, 2, 10));
+		assert_noop!(TokenModule::burn_own(Origin::signed(2), 0, 10), Error::<Test>::TokenPaused);
+		assert_noop!(TokenModule::burn_with_allowance(Origin::signed(3), 0, 2, 10), Error::<Test>::TokenPaused);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn pause_honours_status() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		create_token();
+		assert_ok!(TokenModule::pause(Origin::signed(1), 0, true));
+		assert_ok!(TokenModule::pause(Origin::signed(1), 0, false));
+		assert!(!TokenModule::paused(0));
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn frozen_accounts_cannot_send_or_receive() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		create_token();
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 100));
+		assert_ok!(TokenModule::approve(Origin::signed(2), 0, 3, 50));
+		assert_noop!(TokenModule::freeze(Origin::signed(2), 0, 2), Error::<Test>::NoPermission);
+
+		assert_ok!(TokenModule::freeze(Origin::signed(1), 0, 2));
+		assert_eq!(last_event(), token_event(RawEvent::Frozen(0, 2)));
+		assert_noop!(TokenModule::transfer(Origin::signed(2), 0, 3, 10), Error::<Test>::AccountFrozen);
+		assert_noop!(TokenModule::transfer_from(Origin::signed(3), 0, 2, 3, 10), Error::<Test>::AccountFrozen);
+		assert_noop!(TokenModule::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::AccountFrozen);
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 2, 10), Error::<Test>::AccountFrozen);
+		// Other holders are unaffected.
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 3, 10));
+
+		assert_ok!(TokenModule::thaw(Origin::signed(1), 0, 2));
+		assert_eq!(last_event(), token_event(RawEvent::Thawed(0, 2)));
+		assert_ok!(TokenModule::transfer(Origin::signed(2), 0, 3, 10));
+	});
+}
+
+#[test]
+fn team_roles_gate_privileged_calls() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_eq!(TokenModule::team(0), Some(TokenTeam { issuer: 1, admin: 1, freezer: 1, pauser: 1 }));
+		assert_noop!(TokenModule::set_team(Origin::signed(2), 0, 2, 2, 2, 2), Error::<Test>::NotTokenOwner);
+
+		assert_ok!(TokenModule::set_team(Origin::signed(1), 0, 2, 3, 3, 1));
+		assert_eq!(last_event(), token_event(RawEvent::TeamChanged(0, 2, 3, 3, 1)));
+
+		// Only the issuer mints.
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 1, 10), Error::<Test>::NoPermission);
+		assert_ok!(TokenModule::mint(Origin::signed(2), 0, 2, 10));
+		// Only the admin burns.
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 3, 10));
+		assert_noop!(TokenModule::burn(Origin::signed(2), 0, 5), Error::<Test>::NoPermission);
+		assert_ok!(TokenModule::burn(Origin::signed(3), 0, 5));
+		// Only the freezer freezes and thaws.
+		assert_noop!(TokenModule::freeze(Origin::signed(1), 0, 2), Error::<Test>::NoPermission);
+		assert_ok!(TokenModule::freeze(Origin::signed(3), 0, 2));
+		assert_ok!(TokenModule::thaw(Origin::signed(3), 0, 2));
+		// Only the pauser pauses, so the issuer key cannot halt the token.
+		assert_noop!(TokenModule::pause(Origin::signed(2), 0, true), Error::<Test>::NoPermission);
+		assert_ok!(TokenModule::pause(Origin::signed(1), 0, true));
+		assert_noop!(TokenModule::unpause(Origin::signed(3), 0), Error::<Test>::NoPermission);
+		assert_ok!(TokenModule::unpause(Origin::signed(1), 0));
+	});
+}
+
+#[test]
+fn ownership_transfer_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(TokenModule::transfer_ownership(Origin::signed(2), 0, 2), Error::<Test>::NotTokenOwner);
+
+		assert_ok!(TokenModule::transfer_ownership(Origin::signed(1), 0, 2));
+		assert_eq!(last_event(), token_event(RawEvent::OwnershipTransferProposed(0, 1, 2)));
+		assert_eq!(TokenModule::owner(0), Some(1));
+
+		assert_noop!(TokenModule::accept_ownership(Origin::signed(3), 0), Error::<Test>::NotPendingOwner);
+		assert_ok!(TokenModule::set_team(Origin::signed(1), 0, 1, 3, 3, 1));
+		assert_ok!(TokenModule::accept_ownership(Origin::signed(2), 0));
+		assert_eq!(last_event(), token_event(RawEvent::OwnershipTransferred(0, 1, 2)));
+		assert!(System::events().iter().any(|r| r.event == token_event(RawEvent::TeamChanged(0, 2, 2, 2, 2))));
+		assert_eq!(TokenModule::owner(0), Some(2));
+		assert_eq!(TokenModule::pending_owner(0), None);
+
+		// Every role moves to the new owner.
+		assert_eq!(TokenModule::team(0), Some(TokenTeam { issuer: 2, admin: 2, freezer: 2, pauser: 2 }));
+		assert_noop!(TokenModule::set_team(Origin::signed(1), 0, 1, 1, 1, 1), Error::<Test>::NotTokenOwner);
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 1, 1), Error::<Test>::NoPermission);
+		assert_noop!(TokenModule::freeze(Origin::signed(3), 0, 1), Error::<Test>::NoPermission);
+		assert_ok!(TokenModule::mint(Origin::signed(2), 0, 2, 1));
+		assert_ok!(TokenModule::freeze(Origin::signed(2), 0, 1));
+	});
+}
+
+#[test]
+fn renounce_ownership_works() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::transfer_ownership(Origin::signed(1), 0, 2));
+
+		// Nobody could undo a pause or a freeze once the team is gone.
+		assert_ok!(TokenModule::pause(Origin::signed(1), 0, true));
+		assert_noop!(TokenModule::renounce_ownership(Origin::signed(1), 0), Error::<Test>::TokenPaused);
+		assert_ok!(TokenModule::unpause(Origin::signed(1), 0));
+		assert_ok!(TokenModule::freeze(Origin::signed(1), 0, 3));
+		assert_noop!(TokenModule::renounce_ownership(Origin::signed(1), 0), Error::<Test>::AccountsFrozen);
+		assert_ok!(TokenModule::thaw(Origin::signed(1), 0, 3));
+
+		assert_ok!(TokenModule::renounce_ownership(Origin::signed(1), 0));
+		assert_eq!(last_event(), token_event(RawEvent::OwnershipRenounced(0, 1)));
+
+		assert_eq!(TokenModule::owner(0), None);
+		assert_eq!(TokenModule::team(0), None);
+		assert_noop!(TokenModule::accept_ownership(Origin::signed(2), 0), Error::<Test>::NotPendingOwner);
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 1, 1), Error::<Test>::NoPermission);
+	});
+}
+
+#[test]
+fn set_metadata_adjusts_deposit() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(
+			TokenModule::set_metadata(Origin::signed(2), 0, None, b"desc".to_vec(), Vec::new()),
+			Error::<Test>::NotTokenOwner
+		);
+
 		assert_noop!(
-			TemplateModule::cause_error(Origin::signed(1)),
-			Error::<Test>::NoneValue
+			TokenModule::set_metadata(Origin::signed(1), 0, Some(vec![b'i'; 17]), Vec::new(), Vec::new()),
+			Error::<Test>::IconUriTooLong
 		);
+		assert_noop!(
+			TokenModule::set_metadata(Origin::signed(1), 0, None, vec![b'd'; 65], Vec::new()),
+			Error::<Test>::DescriptionTooLong
+		);
+		assert_noop!(
+			TokenModule::set_metadata(Origin::signed(1), 0, None, Vec::new(), vec![b'w'; 17]),
+			Error::<Test>::WebsiteTooLong
+		);
+
+		assert_ok!(TokenModule::set_metadata(
+			Origin::signed(1), 0, Some(b"ipfs://x".to_vec()), b"desc".to_vec(), b"dcb.io".to_vec()
+		));
+		assert_eq!(last_event(), token_event(RawEvent::MetadataUpdated(0)));
+		assert_eq!(TokenModule::deposit(0), Some((1, 40)));
+		assert_eq!(Balances::reserved_balance(1), 40);
+
+		assert_ok!(TokenModule::set_metadata(Origin::signed(1), 0, None, Vec::new(), Vec::new()));
+		assert_eq!(TokenModule::deposit(0), Some((1, 22)));
+		assert_eq!(Balances::reserved_balance(1), 22);
+	});
+}
+
+#[test]
+fn set_metadata_moves_deposit_to_the_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TokenModule::create(Origin::signed(1), 2, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1000, None, 0));
+		assert_eq!(TokenModule::deposit(0), Some((1, 22)));
+
+		assert_ok!(TokenModule::set_metadata(Origin::signed(2), 0, None, b"desc".to_vec(), Vec::new()));
+		assert_eq!(TokenModule::deposit(0), Some((2, 26)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 26);
+	});
+}
+
+#[test]
+fn tokens_of_lists_holdings() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::create(Origin::signed(2), 2, b"Dollar".to_vec(), b"USDD".to_vec(), 6, 500, None, 0));
+		assert_ok!(TokenModule::transfer(Origin::signed(2), 1, 1, 200));
+
+		let mut holdings = TokenModule::tokens_of(&1);
+		holdings.sort();
+		assert_eq!(holdings, vec![(0, 1000), (1, 200)]);
+		assert_eq!(TokenModule::tokens_of(&2), vec![(1, 300)]);
+		assert!(TokenModule::tokens_of(&3).is_empty());
+	});
+}
+
+#[test]
+fn start_destroy_freezes_the_token() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::approve(Origin::signed(1), 0, 2, 100));
+		assert_noop!(TokenModule::start_destroy(Origin::signed(2), 0), Error::<Test>::NotTokenOwner);
+		assert_noop!(TokenModule::start_destroy(Origin::root(), 1), Error::<Test>::TokenNotFound);
+
+		assert_ok!(TokenModule::start_destroy(Origin::signed(1), 0));
+		assert_eq!(last_event(), token_event(RawEvent::DestructionStarted(0)));
+		assert!(TokenModule::destroying(0));
+
+		assert_noop!(TokenModule::start_destroy(Origin::root(), 0), Error::<Test>::TokenDestroying);
+		assert_noop!(TokenModule::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::TokenDestroying);
+		assert_noop!(TokenModule::transfer_from(Origin::signed(2), 0, 1, 3, 10), Error::<Test>::TokenDestroying);
+		assert_noop!(TokenModule::approve(Origin::signed(1), 0, 3, 10), Error::<Test>::TokenDestroying);
+		assert_noop!(TokenModule::mint(Origin::signed(1), 0, 1, 10), Error::<Test>::TokenDestroying);
+		assert_noop!(TokenModule::burn(Origin::signed(1), 0, 10), Error::<Test>::TokenDestroying);
+	});
+}
+
+#[test]
+fn destroy_removes_accounts_in_batches() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 2, 100));
+		assert_ok!(TokenModule::transfer(Origin::signed(1), 0, 3, 100));
+		assert_ok!(TokenModule::approve(Origin::signed(1), 0, 2, 50));
+		assert_ok!(TokenModule::approve(Origin::signed(2), 0, 3, 50));
+		// A holder and an account holding nothing are frozen.
+		assert_ok!(TokenModule::freeze(Origin::signed(1), 0, 2));
+		assert_ok!(TokenModule::freeze(Origin::signed(1), 0, 4));
+		assert_noop!(TokenModule::destroy_accounts(Origin::signed(3), 0, 10), Error::<Test>::NotDestroying);
+		assert_noop!(TokenModule::destroy_frozen(Origin::signed(3), 0, 10), Error::<Test>::NotDestroying);
+		assert_noop!(TokenModule::finish_destroy(Origin::signed(3), 0), Error::<Test>::NotDestroying);
+
+		assert_ok!(TokenModule::start_destroy(Origin::root(), 0));
+		assert_ok!(TokenModule::destroy_accounts(Origin::signed(3), 0, 2));
+		assert_eq!(last_event(), token_event(RawEvent::AccountsDestroyed(0, 2)));
+		assert_eq!(TokenModule::holders(0).len(), 1);
+		assert_noop!(TokenModule::finish_destroy(Origin::signed(3), 0), Error::<Test>::AccountsRemaining);
+
+		assert_ok!(TokenModule::destroy_accounts(Origin::signed(3), 0, 2));
+		assert_eq!(last_event(), token_event(RawEvent::AccountsDestroyed(0, 1)));
+		assert!(TokenModule::holders(0).is_empty());
+		assert_eq!(TokenModule::supply(0), 0);
+		assert_noop!(TokenModule::finish_destroy(Origin::signed(3), 0), Error::<Test>::ApprovalsRemaining);
+
+		assert_ok!(TokenModule::destroy_approvals(Origin::signed(3), 0, 10));
+		assert_eq!(last_event(), token_event(RawEvent::ApprovalsDestroyed(0, 2)));
+		assert_eq!(TokenModule::approval(0, (1, 2)), 0);
+		assert!(!TokenModule::frozen(0, 2));
+		assert_noop!(TokenModule::finish_destroy(Origin::signed(3), 0), Error::<Test>::FrozenRemaining);
+
+		assert_ok!(TokenModule::destroy_frozen(Origin::signed(3), 0, 10));
+		assert_eq!(last_event(), token_event(RawEvent::FrozenDestroyed(0, 1)));
+		assert!(!TokenModule::frozen(0, 4));
+
+		assert_ok!(TokenModule::finish_destroy(Origin::signed(3), 0));
+		assert_eq!(last_event(), token_event(RawEvent::Destroyed(0)));
+		assert_eq!(TokenModule::tokens(0), None);
+		assert_eq!(TokenModule::owner(0), None);
+		assert_eq!(TokenModule::deposit(0), None);
+		assert!(!TokenModule::destroying(0));
+		assert_eq!(TokenModule::token_by_symbol(b"DCB".to_vec()), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// The symbol can be used again, under a new index.
+		create_token();
+		assert!(TokenModule::tokens(1).is_some());
+	});
+}
+
+// Hash two merkle nodes in sorted order, as `merkle_root` does.
+fn hash_pair(a: H256, b: H256) -> H256 {
+	let (first, second) = if a <= b { (a, b) } else { (b, a) };
+	BlakeTwo256::hash(&[first.as_bytes(), second.as_bytes()].concat())
+}
+
+// Creates token 0 and, from its whole supply, airdrop 0 over three leaves expiring at block 10.
+// Returns the proof of each leaf: `(2, 100)`, `(3, 200)` and `(4, 50)`.
+fn create_airdrop() -> Vec<(u64, u64, Vec<H256>)> {
+	create_token();
+	let leaves = [(2u64, 100u64), (3, 200), (4, 50)];
+	let hashes = leaves.iter().map(BlakeTwo256::hash_of).collect::<Vec<_>>();
+	let left = hash_pair(hashes[0], hashes[1]);
+	let root = hash_pair(left, hashes[2]);
+	assert_ok!(TokenModule::create_airdrop(Origin::signed(1), 0, root, 1000, 10));
+
+	vec![
+		(2, 100, vec![hashes[1], hashes[2]]),
+		(3, 200, vec![hashes[0], hashes[2]]),
+		(4, 50, vec![left]),
+	]
+}
+
+fn validate_claim(airdrop: u32, who: u64, amount: u64, proof: Vec<H256>) -> TransactionValidity {
+	<TokenModule as ValidateUnsigned>::validate_unsigned(
+		TransactionSource::External,
+		&crate::Call::claim(airdrop, who, amount, proof),
+	)
+}
+
+#[test]
+fn create_airdrop_escrows_the_pot() {
+	new_test_ext().execute_with(|| {
+		create_token();
+		assert_noop!(
+			TokenModule::create_airdrop(Origin::signed(2), 0, H256::zero(), 100, 10),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			TokenModule::create_airdrop(Origin::signed(1), 0, H256::zero(), 1001, 10),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			TokenModule::create_airdrop(Origin::signed(1), 0, H256::zero(), 100, 1),
+			Error::<Test>::AirdropExpired
+		);
+
+		assert_ok!(TokenModule::create_airdrop(Origin::signed(1), 0, H256::zero(), 400, 10));
+		assert_eq!(last_event(), token_event(RawEvent::AirdropCreated(0, 0, 1, 400)));
+		assert_eq!(TokenModule::balance(0, 1), 600);
+		assert_eq!(TokenModule::supply(0), 1000);
+		assert_eq!(TokenModule::airdrops(0).unwrap().remaining, 400);
+		assert_eq!(TokenModule::airdrop_count(), 1);
+	});
+}
+
+#[test]
+fn airdrop_claims_work_once() {
+	new_test_ext().execute_with(|| {
+		let claims = create_airdrop();
+		assert_ok!(TokenModule::set_sufficient(Origin::root(), 0, true));
+
+		System::set_block_number(4);
+		for (who, amount, proof) in claims {
+			// The claim stays in the pool until the airdrop expires at block 10.
+			assert_eq!(validate_claim(0, who, amount, proof.clone()).unwrap().longevity, 6);
+			assert_ok!(TokenModule::claim(Origin::none(), 0, who, amount, proof.clone()));
+			assert_eq!(last_event(), token_event(RawEvent::AirdropClaimed(0, who, amount)));
+			assert_eq!(TokenModule::balance(0, who), amount);
+
+			assert_eq!(
+				validate_claim(0, who, amount, proof.clone()),
+				InvalidTransaction::Custom(Error::<Test>::AlreadyClaimed.as_u8()).into()
+			);
+			assert_noop!(TokenModule::claim(Origin::none(), 0, who, amount, proof), Error::<Test>::AlreadyClaimed);
+		}
+		assert_eq!(TokenModule::airdrops(0).unwrap().remaining, 650);
+		assert_eq!(TokenModule::supply(0), 1000);
+	});
+}
+
+#[test]
+fn airdrop_claims_need_a_valid_proof() {
+	new_test_ext().execute_with(|| {
+		let claims = create_airdrop();
+		let (who, amount, proof) = claims[0].clone();
+
+		assert_eq!(
+			validate_claim(0, who, amount + 1, proof.clone()),
+			InvalidTransaction::Custom(Error::<Test>::InvalidProof.as_u8()).into()
+		);
+		assert_noop!(
+			TokenModule::claim(Origin::none(), 0, 3, amount, proof.clone()),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(
+			TokenModule::claim(Origin::signed(2), 0, who, amount, proof.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_eq!(
+			validate_claim(1, who, amount, proof.clone()),
+			InvalidTransaction::Custom(Error::<Test>::AirdropNotFound.as_u8()).into()
+		);
+
+		let long_proof = vec![H256::zero(); 33];
+		assert_eq!(
+			validate_claim(0, who, amount, long_proof.clone()),
+			InvalidTransaction::Custom(Error::<Test>::ProofTooLong.as_u8()).into()
+		);
+		assert_noop!(TokenModule::claim(Origin::none(), 0, who, amount, long_proof), Error::<Test>::ProofTooLong);
+		assert_eq!(
+			<TokenModule as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::transfer(0, 2, 1),
+			),
+			InvalidTransaction::Call.into()
+		);
+	});
+}
+
+#[test]
+fn airdrop_claims_that_would_fail_are_not_valid() {
+	new_test_ext().execute_with(|| {
+		let claims = create_airdrop();
+
+		// Account 4 has no account and the token is not sufficient.
+		let (who, amount, proof) = claims[2].clone();
+		assert_eq!(
+			validate_claim(0, who, amount, proof.clone()),
+			InvalidTransaction::Custom(Error::<Test>::NoAccount.as_u8()).into()
+		);
+		assert_noop!(TokenModule::claim(Origin::none(), 0, who, amount, proof), Error::<Test>::NoAccount);
+
+		let (who, amount, proof) = claims[0].clone();
+		assert_ok!(TokenModule::pause(Origin::signed(1), 0, true));
+		assert_eq!(
+			validate_claim(0, who, amount, proof.clone()),
+			InvalidTransaction::Custom(Error::<Test>::TokenPaused.as_u8()).into()
+		);
+		assert_ok!(TokenModule::unpause(Origin::signed(1), 0));
+
+		assert_ok!(TokenModule::freeze(Origin::signed(1), 0, who));
+		assert_eq!(
+			validate_claim(0, who, amount, proof),
+			InvalidTransaction::Custom(Error::<Test>::AccountFrozen.as_u8()).into()
+		);
+	});
+}
+
+#[test]
+fn expired_airdrops_can_be_reclaimed() {
+	new_test_ext().execute_with(|| {
+		let claims = create_airdrop();
+		let (who, amount, proof) = claims[1].clone();
+		assert_ok!(TokenModule::claim(Origin::none(), 0, who, amount, proof.clone()));
+		assert_noop!(TokenModule::reclaim(Origin::signed(1), 0, 1), Error::<Test>::AirdropNotExpired);
+
+		System::set_block_number(11);
+		let (who, amount, proof) = claims[0].clone();
+		assert_eq!(
+			validate_claim(0, who, amount, proof.clone()),
+			InvalidTransaction::Custom(Error::<Test>::AirdropExpired.as_u8()).into()
+		);
+		assert_noop!(TokenModule::claim(Origin::none(), 0, who, amount, proof), Error::<Test>::AirdropExpired);
+
+		assert_eq!(TokenModule::airdrops(0).unwrap().claims, 1);
+		assert_noop!(TokenModule::reclaim(Origin::signed(2), 0, 1), Error::<Test>::NoPermission);
+		assert_noop!(TokenModule::reclaim(Origin::signed(1), 0, 0), Error::<Test>::BadWitness);
+		assert_ok!(TokenModule::reclaim(Origin::signed(1), 0, 1));
+		assert_eq!(last_event(), token_event(RawEvent::AirdropReclaimed(0, 800)));
+		assert_eq!(TokenModule::balance(0, 1), 800);
+		assert_eq!(TokenModule::airdrops(0), None);
+		assert!(!TokenModule::claimed(0, 3));
+	});
+}
+
+#[test]
+fn airdrops_of_destroyed_tokens_can_be_closed() {
+	new_test_ext().execute_with(|| {
+		let claims = create_airdrop();
+		let (who, amount, proof) = claims[1].clone();
+		assert_ok!(TokenModule::claim(Origin::none(), 0, who, amount, proof));
+
+		assert_ok!(TokenModule::start_destroy(Origin::root(), 0));
+		assert_ok!(TokenModule::destroy_accounts(Origin::signed(3), 0, 10));
+		assert_ok!(TokenModule::finish_destroy(Origin::signed(3), 0));
+		assert_noop!(TokenModule::reclaim(Origin::signed(2), 0, 1), Error::<Test>::NoPermission);
+
+		assert_ok!(TokenModule::reclaim(Origin::signed(1), 0, 1));
+		assert_eq!(last_event(), token_event(RawEvent::AirdropReclaimed(0, 0)));
+		assert_eq!(TokenModule::airdrops(0), None);
+		assert!(!TokenModule::claimed(0, 3));
+		assert_eq!(TokenModule::balance(0, 1), 0);
+	});
+}
+
+#[test]
+fn genesis_config_works() {
+	let tokens = vec![
+		(0, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1),
+		(2, b"Dollar".to_vec(), b"USDD".to_vec(), 6, 2),
+	];
+	let balances = vec![(0, 1, 600), (0, 2, 400), (2, 3, 50)];
+	new_test_ext_with_tokens(tokens, balances).execute_with(|| {
+		assert_eq!(TokenModule::token_count(), 3);
+		assert_eq!(TokenModule::tokens(0).unwrap().decimals, 12);
+		assert!(TokenModule::tokens(1).is_none());
+		assert_eq!(TokenModule::owner(2), Some(2));
+		assert_eq!(TokenModule::token_by_symbol(b"USDD".to_vec()), Some(2));
+		assert_eq!(TokenModule::supply(0), 1000);
+		assert_eq!(TokenModule::supply(2), 50);
+		assert_eq!(TokenModule::balance(0, 2), 400);
+		assert_eq!(StorageVersion::get(), Releases::V9);
+
+		// Later tokens are numbered after the genesis ones.
+		assert_ok!(TokenModule::create(Origin::signed(1), 1, b"Other".to_vec(), b"OTH".to_vec(), 0, 1, None, 0));
+		assert!(TokenModule::tokens(3).is_some());
+	});
+}
+
+#[test]
+#[should_panic(expected = "token supply does not match the sum of balances")]
+fn genesis_config_rejects_duplicate_holders() {
+	let tokens = vec![(0, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1)];
+	new_test_ext_with_tokens(tokens, vec![(0, 1, 600), (0, 1, 400)]);
+}
+
+#[test]
+#[should_panic(expected = "duplicate token symbol in genesis")]
+fn genesis_config_rejects_duplicate_symbols() {
+	let tokens = vec![
+		(0, b"DCB Token".to_vec(), b"DCB".to_vec(), 12, 1),
+		(1, b"Fake DCB".to_vec(), b"DCB".to_vec(), 12, 1),
+	];
+	new_test_ext_with_tokens(tokens, vec![]);
+}
+
+#[test]
+fn migration_from_v1_works() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::put(Releases::V1);
+
+		// Two tokens in the original layout: (name, symbol, owner, created).
+		let v1 = |symbol: &[u8]| (b"DCB Token".to_vec(), symbol.to_vec(), 7u64, 5u64).encode();
+		sp_io::storage::set(&Tokens::<Test>::hashed_key_for(0), &v1(b"DCB"));
+		sp_io::storage::set(&Tokens::<Test>::hashed_key_for(1), &v1(b"DCB"));
+		crate::TokenCount::put(2);
+		crate::Owner::<Test>::insert(0, 1);
+		// Balances and approvals keyed by a single tuple.
+		let old_key = |key: Vec<u8>| Blake2_128Concat::hash(&key);
+		put_storage_value(b"TokenStore", b"Balance", &old_key((0u32, 1u64).encode()), 600u64);
+		put_storage_value(b"TokenStore", b"Balance", &old_key((1u32, 2u64).encode()), 400u64);
+		put_storage_value(b"TokenStore", b"Balance", &old_key((1u32, 3u64).encode()), 0u64);
+		put_storage_value(b"TokenStore", b"Approval", &old_key((0u32, 1u64, 2u64).encode()), 50u64);
+
+		TokenModule::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get(), Releases::V9);
+		let info = TokenModule::tokens(0).unwrap();
+		assert_eq!(info.symbol, b"DCB".to_vec());
+		assert_eq!(info.decimals, 0);
+		assert_eq!(info.max_supply, None);
+		assert_eq!(info.created, 5);
+		assert!(TokenModule::tokens(1).is_some());
+		assert_eq!(TokenModule::owner(0), Some(1));
+		assert_eq!(TokenModule::team(0), Some(TokenTeam { issuer: 1, admin: 1, freezer: 1, pauser: 1 }));
+		assert_eq!(TokenModule::team(1), None);
+		assert_eq!(TokenModule::token_by_symbol(b"DCB".to_vec()), Some(0));
+		assert_eq!(TokenModule::holders(0), vec![(1, 600)]);
+		assert_eq!(TokenModule::balance(1, 2), 400);
+		assert!(!Balance::<Test>::contains_key(1, 3));
+		assert_eq!(System::refs(&1), 1);
+		assert_eq!(System::refs(&2), 1);
+		assert_eq!(System::refs(&3), 0);
+		assert_eq!(TokenModule::approval(0, (1, 2)), 50);
 	});
 }